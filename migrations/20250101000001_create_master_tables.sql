@@ -0,0 +1,40 @@
+-- Master data: country -> state -> city
+create table if not exists country (
+    country_id tinyint unsigned not null auto_increment,
+    country_code varchar(10) not null,
+    country_name varchar(100) not null,
+    created_on datetime not null default current_timestamp,
+    created_by varchar(50) not null,
+    updated_on datetime null on update current_timestamp,
+    updated_by varchar(50) null,
+    primary key (country_id),
+    unique key uq_country_code (country_code)
+) engine = InnoDB default charset = utf8mb4;
+
+create table if not exists state (
+    state_id tinyint unsigned not null auto_increment,
+    state_code varchar(10) not null,
+    state_name varchar(100) not null,
+    country_id tinyint unsigned not null,
+    created_on datetime not null default current_timestamp,
+    created_by varchar(50) not null,
+    updated_on datetime null on update current_timestamp,
+    updated_by varchar(50) null,
+    primary key (state_id),
+    unique key uq_state_code (state_code),
+    constraint fk_state_country foreign key (country_id) references country (country_id)
+) engine = InnoDB default charset = utf8mb4;
+
+create table if not exists city (
+    city_id tinyint unsigned not null auto_increment,
+    city_code varchar(10) not null,
+    city_name varchar(100) not null,
+    state_id tinyint unsigned not null,
+    created_on datetime not null default current_timestamp,
+    created_by varchar(50) not null,
+    updated_on datetime null on update current_timestamp,
+    updated_by varchar(50) null,
+    primary key (city_id),
+    unique key uq_city_code (city_code),
+    constraint fk_city_state foreign key (state_id) references state (state_id)
+) engine = InnoDB default charset = utf8mb4;
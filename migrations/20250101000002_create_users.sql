@@ -0,0 +1,7 @@
+create table if not exists users (
+    user_id varchar(50) not null,
+    password varchar(255) not null,
+    created_on datetime not null default current_timestamp,
+    updated_on datetime null on update current_timestamp,
+    primary key (user_id)
+) engine = InnoDB default charset = utf8mb4;
@@ -0,0 +1,48 @@
+-- Update/delete history, the handlers set @userName and @reason on the session before mutating
+create table if not exists audit_trail (
+    audit_id bigint unsigned not null auto_increment,
+    table_name varchar(64) not null,
+    record_id int unsigned not null,
+    action varchar(10) not null,
+    changed_by varchar(50) null,
+    reason varchar(255) null,
+    old_values json null,
+    new_values json null,
+    changed_on datetime not null default current_timestamp,
+    primary key (audit_id),
+    key ix_audit_trail_record (table_name, record_id),
+    key ix_audit_trail_changed_on (changed_on)
+) engine = InnoDB default charset = utf8mb4;
+
+create trigger country_after_update after update on country for each row
+    insert into audit_trail (table_name, record_id, action, changed_by, reason, old_values, new_values)
+    values ('country', old.country_id, 'update', @userName, @reason,
+        json_object('country_code', old.country_code, 'country_name', old.country_name),
+        json_object('country_code', new.country_code, 'country_name', new.country_name));
+
+create trigger country_after_delete after delete on country for each row
+    insert into audit_trail (table_name, record_id, action, changed_by, reason, old_values, new_values)
+    values ('country', old.country_id, 'delete', @userName, @reason,
+        json_object('country_code', old.country_code, 'country_name', old.country_name), null);
+
+create trigger state_after_update after update on state for each row
+    insert into audit_trail (table_name, record_id, action, changed_by, reason, old_values, new_values)
+    values ('state', old.state_id, 'update', @userName, @reason,
+        json_object('state_code', old.state_code, 'state_name', old.state_name, 'country_id', old.country_id),
+        json_object('state_code', new.state_code, 'state_name', new.state_name, 'country_id', new.country_id));
+
+create trigger state_after_delete after delete on state for each row
+    insert into audit_trail (table_name, record_id, action, changed_by, reason, old_values, new_values)
+    values ('state', old.state_id, 'delete', @userName, @reason,
+        json_object('state_code', old.state_code, 'state_name', old.state_name, 'country_id', old.country_id), null);
+
+create trigger city_after_update after update on city for each row
+    insert into audit_trail (table_name, record_id, action, changed_by, reason, old_values, new_values)
+    values ('city', old.city_id, 'update', @userName, @reason,
+        json_object('city_code', old.city_code, 'city_name', old.city_name, 'state_id', old.state_id),
+        json_object('city_code', new.city_code, 'city_name', new.city_name, 'state_id', new.state_id));
+
+create trigger city_after_delete after delete on city for each row
+    insert into audit_trail (table_name, record_id, action, changed_by, reason, old_values, new_values)
+    values ('city', old.city_id, 'delete', @userName, @reason,
+        json_object('city_code', old.city_code, 'city_name', old.city_name, 'state_id', old.state_id), null);
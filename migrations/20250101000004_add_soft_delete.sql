@@ -0,0 +1,52 @@
+-- Soft delete: rows are stamped instead of removed, list endpoints hide them unless asked
+alter table country
+    add column deleted_on datetime null,
+    add column deleted_by varchar(50) null;
+
+alter table state
+    add column deleted_on datetime null,
+    add column deleted_by varchar(50) null;
+
+alter table city
+    add column deleted_on datetime null,
+    add column deleted_by varchar(50) null;
+
+-- Record soft deletes and restores under their own action instead of 'update'
+drop trigger if exists country_after_update;
+create trigger country_after_update after update on country for each row
+    insert into audit_trail (table_name, record_id, action, changed_by, reason, old_values, new_values)
+    values ('country', old.country_id,
+        case
+            when old.deleted_on is null and new.deleted_on is not null then 'delete'
+            when old.deleted_on is not null and new.deleted_on is null then 'restore'
+            else 'update'
+        end,
+        @userName, @reason,
+        json_object('country_code', old.country_code, 'country_name', old.country_name, 'deleted_on', old.deleted_on),
+        json_object('country_code', new.country_code, 'country_name', new.country_name, 'deleted_on', new.deleted_on));
+
+drop trigger if exists state_after_update;
+create trigger state_after_update after update on state for each row
+    insert into audit_trail (table_name, record_id, action, changed_by, reason, old_values, new_values)
+    values ('state', old.state_id,
+        case
+            when old.deleted_on is null and new.deleted_on is not null then 'delete'
+            when old.deleted_on is not null and new.deleted_on is null then 'restore'
+            else 'update'
+        end,
+        @userName, @reason,
+        json_object('state_code', old.state_code, 'state_name', old.state_name, 'country_id', old.country_id, 'deleted_on', old.deleted_on),
+        json_object('state_code', new.state_code, 'state_name', new.state_name, 'country_id', new.country_id, 'deleted_on', new.deleted_on));
+
+drop trigger if exists city_after_update;
+create trigger city_after_update after update on city for each row
+    insert into audit_trail (table_name, record_id, action, changed_by, reason, old_values, new_values)
+    values ('city', old.city_id,
+        case
+            when old.deleted_on is null and new.deleted_on is not null then 'delete'
+            when old.deleted_on is not null and new.deleted_on is null then 'restore'
+            else 'update'
+        end,
+        @userName, @reason,
+        json_object('city_code', old.city_code, 'city_name', old.city_name, 'state_id', old.state_id, 'deleted_on', old.deleted_on),
+        json_object('city_code', new.city_code, 'city_name', new.city_name, 'state_id', new.state_id, 'deleted_on', new.deleted_on));
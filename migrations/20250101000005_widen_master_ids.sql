@@ -0,0 +1,20 @@
+-- tinyint ids capped every master table at 255 rows
+alter table city drop foreign key fk_city_state;
+alter table state drop foreign key fk_state_country;
+
+alter table country
+    modify country_id int unsigned not null auto_increment;
+
+alter table state
+    modify state_id int unsigned not null auto_increment,
+    modify country_id int unsigned not null;
+
+alter table city
+    modify city_id int unsigned not null auto_increment,
+    modify state_id int unsigned not null;
+
+alter table state
+    add constraint fk_state_country foreign key (country_id) references country (country_id);
+
+alter table city
+    add constraint fk_city_state foreign key (state_id) references state (state_id);
@@ -0,0 +1,12 @@
+-- Audit rows are now written by the application on the connection of the business
+-- transaction. The triggers read session variables that could be left over from
+-- another request on a pooled connection.
+drop trigger if exists country_after_update;
+drop trigger if exists country_after_delete;
+drop trigger if exists state_after_update;
+drop trigger if exists state_after_delete;
+drop trigger if exists city_after_update;
+drop trigger if exists city_after_delete;
+
+alter table audit_trail
+    add column changes json null after new_values;
@@ -0,0 +1,3 @@
+-- Reason code chosen for the change, next to the free text reason
+alter table audit_trail
+    add column reason_code varchar(30) null after reason;
@@ -0,0 +1,24 @@
+-- Catalog of reason codes accepted on updates, deletes and restores, scoped by action
+create table if not exists reason_code (
+    reason_code_id int unsigned not null auto_increment,
+    code varchar(30) not null,
+    description varchar(255) not null,
+    action enum('update', 'delete', 'cancel', 'scrap') not null,
+    active tinyint(1) not null default 1,
+    created_on datetime not null default current_timestamp,
+    created_by varchar(50) not null,
+    updated_on datetime null on update current_timestamp,
+    updated_by varchar(50) null,
+    deleted_on datetime null,
+    deleted_by varchar(50) null,
+    primary key (reason_code_id),
+    unique key uq_reason_code_code (code),
+    key ix_reason_code_action (action, active)
+) engine = InnoDB default charset = utf8mb4;
+
+insert into reason_code (code, description, action, created_by) values
+    ('DATA_FIX', 'Correction of wrongly entered data', 'update', 'system'),
+    ('RENAME', 'Official name or code change', 'update', 'system'),
+    ('RESTORE', 'Record deleted by mistake', 'update', 'system'),
+    ('DUPLICATE', 'Duplicate of another record', 'delete', 'system'),
+    ('OBSOLETE', 'No longer in use', 'delete', 'system');
@@ -0,0 +1,59 @@
+-- Normalized copies of the columns under a uniqueness rule, written by the application
+-- with the same trim, whitespace collapse and lowercasing it checks with. Binary collation
+-- keeps the index from folding accents the application tells apart.
+alter table country
+    add column country_code_key varchar(10) collate utf8mb4_bin null after country_code,
+    add column country_name_key varchar(100) collate utf8mb4_bin null after country_name;
+
+alter table state
+    add column state_code_key varchar(10) collate utf8mb4_bin null after state_code,
+    add column state_name_key varchar(100) collate utf8mb4_bin null after state_name;
+
+alter table city
+    add column city_code_key varchar(10) collate utf8mb4_bin null after city_code,
+    add column city_name_key varchar(100) collate utf8mb4_bin null after city_name;
+
+alter table reason_code
+    add column code_key varchar(30) collate utf8mb4_bin null after code,
+    add column description_key varchar(255) collate utf8mb4_bin null after description;
+
+-- Existing rows; ASCII whitespace only, the next write of a row stores the exact key
+update country set
+    country_code_key = lower(trim(regexp_replace(country_code, '[[:space:]]+', ' '))),
+    country_name_key = lower(trim(regexp_replace(country_name, '[[:space:]]+', ' ')));
+
+update state set
+    state_code_key = lower(trim(regexp_replace(state_code, '[[:space:]]+', ' '))),
+    state_name_key = lower(trim(regexp_replace(state_name, '[[:space:]]+', ' ')));
+
+update city set
+    city_code_key = lower(trim(regexp_replace(city_code, '[[:space:]]+', ' '))),
+    city_name_key = lower(trim(regexp_replace(city_name, '[[:space:]]+', ' ')));
+
+update reason_code set
+    code_key = lower(trim(regexp_replace(code, '[[:space:]]+', ' '))),
+    description_key = lower(trim(regexp_replace(description, '[[:space:]]+', ' ')));
+
+alter table country
+    modify country_code_key varchar(10) collate utf8mb4_bin not null,
+    modify country_name_key varchar(100) collate utf8mb4_bin not null,
+    add unique key uq_country_code_key (country_code_key),
+    add unique key uq_country_name_key (country_name_key);
+
+alter table state
+    modify state_code_key varchar(10) collate utf8mb4_bin not null,
+    modify state_name_key varchar(100) collate utf8mb4_bin not null,
+    add unique key uq_state_code_key (state_code_key),
+    add unique key uq_state_name_key (country_id, state_name_key);
+
+alter table city
+    modify city_code_key varchar(10) collate utf8mb4_bin not null,
+    modify city_name_key varchar(100) collate utf8mb4_bin not null,
+    add unique key uq_city_code_key (city_code_key),
+    add unique key uq_city_name_key (state_id, city_name_key);
+
+alter table reason_code
+    modify code_key varchar(30) collate utf8mb4_bin not null,
+    modify description_key varchar(255) collate utf8mb4_bin not null,
+    add unique key uq_reason_code_code_key (code_key),
+    add unique key uq_reason_code_description_key (action, description_key);
@@ -0,0 +1,117 @@
+use std::{env, error::Error, path::Path, process::ExitCode};
+
+use rust_precise::modules::auth::{create_jwt, hash_password, insert_user, update_password, user_exists};
+use rust_precise::modules::helper::seed::seed::{load_seed, read_seed_file};
+use rust_precise::schemas::helper::seed_schema::{MasterSeed, SeedSummary};
+use rust_precise::settings::{connect_pool, get_configuration, migrate, Settings};
+use sqlx::MySqlPool;
+
+const USAGE: &str = "Usage: precise-admin <command>
+
+Commands:
+    migrate [--dry-run]                   Apply pending schema migrations
+    create-user <user_id> <password>      Create a login for precise.users
+    reset-password <user_id> <password>   Replace the password of an existing user
+    seed [<file>...]                      Upsert country/state/city data from .json or
+                                          countries*/states*/cities*.csv files, defaults
+                                          to the bundled seeds/master_data.json
+    token <user_id>                       Print a signed JWT for an existing user";
+
+const MASTER_DATA_SEED: &str = include_str!("../../seeds/master_data.json");
+
+type AdminResult = Result<(), Box<dyn Error>>;
+
+#[actix_web::main]
+async fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let Some(command) = args.first() else {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    };
+
+    let settings = match get_configuration() {
+        Ok(settings) => settings,
+        Err(err) => {
+            eprintln!("Failed to read configuration: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let pool = connect_pool(&settings.database).await;
+
+    let result = match (command.as_str(), &args[1..]) {
+        ("migrate", rest) => migrate(&pool, rest.iter().any(|arg| arg == "--dry-run")).await.map_err(Into::into),
+        ("create-user", [user_id, password]) => create_user(&pool, user_id, password).await,
+        ("reset-password", [user_id, password]) => reset_password(&pool, user_id, password).await,
+        ("seed", files) => seed(&pool, files).await,
+        ("token", [user_id]) => token(&pool, &settings, user_id).await,
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn create_user(pool: &MySqlPool, user_id: &str, password: &str) -> AdminResult {
+    if user_exists(pool, user_id).await? {
+        return Err(format!("user {} already exists", user_id).into());
+    }
+
+    insert_user(pool, user_id, &hash_password(password)?).await?;
+    println!("Created user {}", user_id);
+    Ok(())
+}
+
+async fn reset_password(pool: &MySqlPool, user_id: &str, password: &str) -> AdminResult {
+    if !update_password(pool, user_id, &hash_password(password)?).await? {
+        return Err(format!("user {} does not exist", user_id).into());
+    }
+
+    println!("Password reset for {}", user_id);
+    Ok(())
+}
+
+async fn seed(pool: &MySqlPool, files: &[String]) -> AdminResult {
+    let mut seeds: Vec<(String, MasterSeed)> = Vec::new();
+    if files.is_empty() {
+        seeds.push(("seeds/master_data.json".into(), serde_json::from_str(MASTER_DATA_SEED)?));
+    }
+    for file in files {
+        seeds.push((file.clone(), read_seed_file(Path::new(file))?));
+    }
+
+    for (file, seed) in seeds {
+        let report = load_seed(pool, &seed, "precise-admin").await?;
+        println!("{}", file);
+        print_summary("countries", &report.countries);
+        print_summary("states", &report.states);
+        print_summary("cities", &report.cities);
+    }
+    Ok(())
+}
+
+fn print_summary(entity: &str, summary: &SeedSummary) {
+    println!(
+        "  {:<10} inserted {}, updated {}, skipped {}, rejected {}",
+        entity, summary.inserted, summary.updated, summary.skipped, summary.rejected.len()
+    );
+    for rejected in &summary.rejected {
+        println!("    {}", rejected);
+    }
+}
+
+async fn token(pool: &MySqlPool, settings: &Settings, user_id: &str) -> AdminResult {
+    if !user_exists(pool, user_id).await? {
+        return Err(format!("user {} does not exist", user_id).into());
+    }
+
+    println!("{}", create_jwt(user_id, &settings.jwt_secret).await);
+    Ok(())
+}
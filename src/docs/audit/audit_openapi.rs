@@ -0,0 +1,54 @@
+pub mod audit_openapi{
+    use crate::docs::master::master_openapi::master_openapi::SecurityAddon;
+    use crate::schemas::api_schemas::Pagination;
+    use crate::schemas::audit::audit_schema::AuditEntrySchema;
+
+    pub mod audit_paths {
+        #[allow(unused_imports)]
+        use crate::schemas::api_schemas::ApiResponse;
+        #[allow(unused_imports)]
+        use crate::schemas::audit::audit_schema::{AuditEntrySchema, AuditQuery};
+
+        #[utoipa::path(
+            get,
+            path = "/precise/api/audit",
+            tag = "Audit",
+            params(AuditQuery),
+            responses(
+                (status = 200, description="Changes across every audited table, newest first", body = ApiResponse<Vec<AuditEntrySchema>>),
+                (status = 400, description="Invalid paging or date range", body = ApiResponse<String>),
+                (status = 500, description="Failed to retrieve data", body = ApiResponse<String>)
+            ),
+            security(("bearer_auth" = []))
+        )]
+        pub fn get_audit() {}
+    }
+
+    /// OpenAPI Documentation for Precise
+    #[derive(utoipa::OpenApi)]
+    #[openapi(
+        modifiers(&SecurityAddon),
+        security(
+            ("bearerAuth"= [])
+        ),
+        paths(audit_paths::get_audit),
+        components(schemas(AuditEntrySchema, Pagination)),
+        tags(
+            (name = "Precise API", description="API for Precise")
+        ),
+        info(
+            title = "Precise API Service",
+            version = "1.0.0",
+            license(
+                name = "MIT"
+            ),
+            contact(
+                name = "PT Presindo Central",
+                url = "https://www.onyxhouseware.com",
+                email = "smart.presindo@gmail.com"
+            ),
+            description = "Precise API for PT Presindo Central"
+        ),
+    )]
+    pub struct AuditApiDoc;
+}
@@ -0,0 +1 @@
+pub mod audit_openapi;
\ No newline at end of file
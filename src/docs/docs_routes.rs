@@ -3,7 +3,8 @@ pub mod docs_routes{
     use serde_json::Value;
     use utoipa::OpenApi;
     use utoipa_swagger_ui::SwaggerUi;
-    use crate::docs::master::{country_openapi::country_openapi::CountryApiDoc, state_openapi::state_openapi::StateApiDoc, city_openapi::city_openapi::CityApiDoc};
+    use crate::docs::audit::audit_openapi::audit_openapi::AuditApiDoc;
+    use crate::docs::master::master_openapi::master_openapi::{CountryApiDoc, StateApiDoc, CityApiDoc, ReasonCodeApiDoc, HierarchyApiDoc};
 
 
     pub fn docs_routes(cfg: &mut web::ServiceConfig) {
@@ -18,6 +19,15 @@ pub mod docs_routes{
             .service(
                 SwaggerUi::new("/api/city/{_:.*}").url(path_json_file("master","city"), CityApiDoc::openapi())
             )
+            .service(
+                SwaggerUi::new("/api/reason-code/{_:.*}").url(path_json_file("master","reason-code"), ReasonCodeApiDoc::openapi())
+            )
+            .service(
+                SwaggerUi::new("/api/hierarchy/{_:.*}").url(path_json_file("master","hierarchy"), HierarchyApiDoc::openapi())
+            )
+        )
+        .service(
+            SwaggerUi::new("/audit/api/{_:.*}").url(path_json_file("audit","audit"), AuditApiDoc::openapi())
         );
     }
 
@@ -27,6 +37,9 @@ pub mod docs_routes{
             .route(&path_json_file("master","country"), web::get().to(openapi_json::<CountryApiDoc>))
             .route(&path_json_file("master","state"), web::get().to(openapi_json::<StateApiDoc>))
             .route(&path_json_file("master","city"), web::get().to(openapi_json::<CityApiDoc>))
+            .route(&path_json_file("master","reason-code"), web::get().to(openapi_json::<ReasonCodeApiDoc>))
+            .route(&path_json_file("master","hierarchy"), web::get().to(openapi_json::<HierarchyApiDoc>))
+            .route(&path_json_file("audit","audit"), web::get().to(openapi_json::<AuditApiDoc>))
         );
     }
 
@@ -0,0 +1,466 @@
+pub mod master_openapi{
+    use utoipa::openapi::security::HttpAuthScheme;
+    use utoipa::openapi::security::SecurityScheme;
+    use utoipa::Modify;
+
+    /// Generates the OpenAPI document for one master entity served by `master_resource`.
+    /// utoipa reads attribute arguments as tokens, so every path is spelled out as a literal.
+    macro_rules! master_openapi {
+        (
+            $doc:ident, $stubs:ident, $tag:literal,
+            row: $row:ident, insert: $insert:ident, update: $update:ident,
+            code_query: $code_query:ident, name_query: $name_query:ident,
+            list: $list:literal, item: $item:literal, by_code: $by_code:literal, resolve: $resolve:literal, export: $export:literal, import: $import:literal, restore: $restore:literal, dependents: $dependents:literal, history: $history:literal, exists_code: $exists_code:literal, exists_name: $exists_name:literal
+        ) => {
+            pub mod $stubs {
+                #[allow(unused_imports)]
+                use crate::schemas::api_schemas::ApiResponse;
+                #[allow(unused_imports)]
+                use crate::schemas::audit::audit_schema::{AuditEntrySchema, HistoryQuery};
+                #[allow(unused_imports)]
+                use crate::schemas::helper::reason_schema::{DeleteReasonSchema, RestoreReasonSchema};
+                #[allow(unused_imports)]
+                use crate::schemas::master::dependent_schema::{DeleteQuery, DependentSchema};
+                #[allow(unused_imports)]
+                use crate::schemas::master::export_schema::ExportQuery;
+                #[allow(unused_imports)]
+                use crate::schemas::master::import_schema::{ImportQuery, ImportReportSchema};
+                #[allow(unused_imports)]
+                use crate::schemas::master::list_schema::ListQueryParams;
+                #[allow(unused_imports)]
+                use crate::schemas::master::resolve_schema::{ResolveCodesSchema, ResolvedCodesSchema};
+                #[allow(unused_imports)]
+                use super::*;
+
+                #[utoipa::path(
+                    get,
+                    path = $list,
+                    tag = $tag,
+                    params(ListQueryParams),
+                    responses(
+                        (status = 200, description="Data retrieved successfully", body = ApiResponse<Vec<$row>>),
+                        (status = 400, description="Unknown sort or filter field", body = ApiResponse<String>),
+                        (status = 500, description="Failed to retrieve data", body = ApiResponse<String>)
+                    ),
+                    security(("bearer_auth" = []))
+                )]
+                pub fn get_all() {}
+
+                #[utoipa::path(
+                    post,
+                    path = $list,
+                    tag = $tag,
+                    request_body = $insert,
+                    responses(
+                        (status = 200, description="Data inserted successfully", body = ApiResponse<$row>),
+                        (status = 400, description="Invalid input, unknown parent or code already used", body = ApiResponse<String>),
+                        (status = 409, description="Code taken by a concurrent request", body = ApiResponse<String>),
+                        (status = 422, description="Referenced record does not exist", body = ApiResponse<String>),
+                        (status = 500, description="Failed to insert data", body = ApiResponse<String>)
+                    ),
+                    security(("bearer_auth" = []))
+                )]
+                pub fn create() {}
+
+                #[utoipa::path(
+                    get,
+                    path = $item,
+                    tag = $tag,
+                    params(("id" = u32, Path, description = "Record id")),
+                    responses(
+                        (status = 200, description="Data retrieved successfully", body = ApiResponse<$row>),
+                        (status = 404, description="Data not found", body = ApiResponse<String>),
+                        (status = 500, description="Failed to retrieve data", body = ApiResponse<String>)
+                    ),
+                    security(("bearer_auth" = []))
+                )]
+                pub fn get_by_id() {}
+
+                #[utoipa::path(
+                    get,
+                    path = $by_code,
+                    tag = $tag,
+                    params(("code" = String, Path, description = "Business code, matched like exists/code")),
+                    responses(
+                        (status = 200, description="Data retrieved successfully", body = ApiResponse<$row>),
+                        (status = 404, description="Data not found", body = ApiResponse<String>),
+                        (status = 500, description="Failed to retrieve data", body = ApiResponse<String>)
+                    ),
+                    security(("bearer_auth" = []))
+                )]
+                pub fn get_by_code() {}
+
+                #[utoipa::path(
+                    post,
+                    path = $resolve,
+                    tag = $tag,
+                    request_body = ResolveCodesSchema,
+                    responses(
+                        (status = 200, description="Ids of the known codes and the list of unknown ones", body = ApiResponse<ResolvedCodesSchema>),
+                        (status = 400, description="Empty or oversized list of codes", body = ApiResponse<String>),
+                        (status = 500, description="Failed to resolve codes", body = ApiResponse<String>)
+                    ),
+                    security(("bearer_auth" = []))
+                )]
+                pub fn resolve() {}
+
+                #[utoipa::path(
+                    get,
+                    path = $export,
+                    tag = $tag,
+                    params(ExportQuery, ListQueryParams),
+                    responses(
+                        (status = 200, description="Attachment named after the entity and date, e.g. countries-20250101.csv, streamed as rows are read",
+                            content(
+                                (String = "text/csv"),
+                                (String = "application/x-ndjson"),
+                                (Vec<u8> = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet")
+                            )
+                        ),
+                        (status = 400, description="Unknown format, sort or filter field", body = ApiResponse<String>)
+                    ),
+                    security(("bearer_auth" = []))
+                )]
+                pub fn export() {}
+
+                #[utoipa::path(
+                    post,
+                    path = $import,
+                    tag = $tag,
+                    params(ImportQuery),
+                    request_body(
+                        description = "CSV or XLSX file with a header row, parents named by their code column",
+                        content(
+                            (String = "text/csv"),
+                            (Vec<u8> = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet")
+                        )
+                    ),
+                    responses(
+                        (status = 200, description="Per row report, valid rows written when mode is commit", body = ApiResponse<ImportReportSchema>),
+                        (status = 400, description="Unreadable file, missing code column, too many rows or missing reason for updates", body = ApiResponse<String>),
+                        (status = 409, description="Code taken by a concurrent request, nothing was written", body = ApiResponse<String>),
+                        (status = 413, description="File too large", body = ApiResponse<String>),
+                        (status = 500, description="Failed to import data", body = ApiResponse<String>)
+                    ),
+                    security(("bearer_auth" = []))
+                )]
+                pub fn import() {}
+
+                #[utoipa::path(
+                    put,
+                    path = $item,
+                    tag = $tag,
+                    params(("id" = u32, Path, description = "Record id")),
+                    request_body = $update,
+                    responses(
+                        (status = 200, description="Data updated successfully", body = ApiResponse<$row>),
+                        (status = 400, description="Invalid input, missing reason, unknown reason code, unknown parent or code already used", body = ApiResponse<String>),
+                        (status = 404, description="Data not found", body = ApiResponse<String>),
+                        (status = 409, description="Record is deleted or code taken by a concurrent request", body = ApiResponse<String>),
+                        (status = 500, description="Failed to update data", body = ApiResponse<String>)
+                    ),
+                    security(("bearer_auth" = []))
+                )]
+                pub fn update() {}
+
+                #[utoipa::path(
+                    patch,
+                    path = $item,
+                    tag = $tag,
+                    params(("id" = u32, Path, description = "Record id")),
+                    request_body(
+                        content = Object,
+                        content_type = "application/merge-patch+json",
+                        description = "JSON merge patch of the update fields, `reason` or `reason_code` is required"
+                    ),
+                    responses(
+                        (status = 200, description="Data updated successfully", body = ApiResponse<$row>),
+                        (status = 400, description="Invalid patch, missing reason, unknown reason code, unknown parent or code already used", body = ApiResponse<String>),
+                        (status = 404, description="Data not found", body = ApiResponse<String>),
+                        (status = 409, description="Record is deleted or code taken by a concurrent request", body = ApiResponse<String>),
+                        (status = 500, description="Failed to update data", body = ApiResponse<String>)
+                    ),
+                    security(("bearer_auth" = []))
+                )]
+                pub fn patch() {}
+
+                #[utoipa::path(
+                    delete,
+                    path = $item,
+                    tag = $tag,
+                    params(("id" = u32, Path, description = "Record id"), DeleteQuery),
+                    request_body = DeleteReasonSchema,
+                    responses(
+                        (status = 200, description="Data deleted successfully", body = ApiResponse<$row>),
+                        (status = 400, description="Missing or short reason, unknown reason code or missing deleted_by", body = ApiResponse<String>),
+                        (status = 404, description="Data not found", body = ApiResponse<String>),
+                        (status = 409, description="Data is already deleted or still has dependents", body = ApiResponse<Vec<DependentSchema>>),
+                        (status = 500, description="Failed to delete data", body = ApiResponse<String>)
+                    ),
+                    security(("bearer_auth" = []))
+                )]
+                pub fn delete() {}
+
+                #[utoipa::path(
+                    get,
+                    path = $dependents,
+                    tag = $tag,
+                    params(("id" = u32, Path, description = "Record id")),
+                    responses(
+                        (status = 200, description="Live records referencing this one", body = ApiResponse<Vec<DependentSchema>>),
+                        (status = 404, description="Data not found", body = ApiResponse<String>),
+                        (status = 500, description="Failed to retrieve data", body = ApiResponse<String>)
+                    ),
+                    security(("bearer_auth" = []))
+                )]
+                pub fn get_dependents() {}
+
+                #[utoipa::path(
+                    post,
+                    path = $restore,
+                    tag = $tag,
+                    params(("id" = u32, Path, description = "Record id")),
+                    request_body = RestoreReasonSchema,
+                    responses(
+                        (status = 200, description="Data restored successfully", body = ApiResponse<$row>),
+                        (status = 400, description="Missing or short reason, unknown reason code or missing updated_by", body = ApiResponse<String>),
+                        (status = 404, description="Data not found", body = ApiResponse<String>),
+                        (status = 409, description="Data is not deleted or its parent is deleted", body = ApiResponse<String>),
+                        (status = 500, description="Failed to restore data", body = ApiResponse<String>)
+                    ),
+                    security(("bearer_auth" = []))
+                )]
+                pub fn restore() {}
+
+                #[utoipa::path(
+                    get,
+                    path = $history,
+                    tag = $tag,
+                    params(("id" = u32, Path, description = "Record id"), HistoryQuery),
+                    responses(
+                        (status = 200, description="Changes of the record, newest first", body = ApiResponse<Vec<AuditEntrySchema>>),
+                        (status = 400, description="Invalid paging or date range", body = ApiResponse<String>),
+                        (status = 404, description="Data not found", body = ApiResponse<String>),
+                        (status = 500, description="Failed to retrieve data", body = ApiResponse<String>)
+                    ),
+                    security(("bearer_auth" = []))
+                )]
+                pub fn get_history() {}
+
+                #[utoipa::path(
+                    get,
+                    path = $exists_code,
+                    tag = $tag,
+                    params($code_query),
+                    responses(
+                        (status = 200, description="true when the code exists, false otherwise", body = ApiResponse<bool>),
+                        (status = 400, description="Missing query parameter", body = ApiResponse<String>),
+                        (status = 500, description="Failed to check code", body = ApiResponse<String>)
+                    ),
+                    security(("bearer_auth" = []))
+                )]
+                pub fn exists_code() {}
+
+                #[utoipa::path(
+                    get,
+                    path = $exists_name,
+                    tag = $tag,
+                    params($name_query),
+                    responses(
+                        (status = 200, description="true when the name exists, false otherwise", body = ApiResponse<bool>),
+                        (status = 400, description="Missing query parameter", body = ApiResponse<String>),
+                        (status = 500, description="Failed to check name", body = ApiResponse<String>)
+                    ),
+                    security(("bearer_auth" = []))
+                )]
+                pub fn exists_name() {}
+            }
+
+            /// OpenAPI Documentation for Precise
+            #[derive(utoipa::OpenApi)]
+            #[openapi(
+                modifiers(&SecurityAddon),
+                security(
+                    ("bearerAuth"= [])
+                ),
+                paths($stubs::get_all, $stubs::create, $stubs::get_by_id, $stubs::get_by_code, $stubs::resolve, $stubs::export, $stubs::import, $stubs::update, $stubs::patch, $stubs::delete, $stubs::get_dependents, $stubs::restore, $stubs::get_history, $stubs::exists_code, $stubs::exists_name),
+                components(schemas($row, $insert, $update, $code_query, $name_query, Pagination, DeleteReasonSchema, RestoreReasonSchema, DependentSchema, ResolveCodesSchema, ResolvedCodesSchema, ImportReportSchema, AuditEntrySchema)),
+                tags(
+                    (name = "Precise API", description="API for Precise")
+                ),
+                info(
+                    title = "Precise API Service",
+                    version = "1.0.0",
+                    license(
+                        name = "MIT"
+                    ),
+                    contact(
+                        name = "PT Presindo Central",
+                        url = "https://www.onyxhouseware.com",
+                        email = "smart.presindo@gmail.com"
+                    ),
+                    description = "Precise API for PT Presindo Central"
+                ),
+            )]
+            pub struct $doc;
+        };
+    }
+
+    use crate::schemas::api_schemas::Pagination;
+    use crate::schemas::audit::audit_schema::AuditEntrySchema;
+    use crate::schemas::helper::reason_schema::{DeleteReasonSchema, RestoreReasonSchema};
+    use crate::schemas::master::city_schema::{CitiesSchema, CityCodeQuery, CityNameQuery, InsertCitySchema, UpdateCitySchema};
+    use crate::schemas::master::country_schema::{CountryCodeQuery, CountryNameQuery, CountrySchema, InsertCountrySchema, UpdateCountrySchema};
+    use crate::schemas::master::dependent_schema::DependentSchema;
+    use crate::schemas::master::import_schema::ImportReportSchema;
+    use crate::schemas::master::resolve_schema::{ResolveCodesSchema, ResolvedCodesSchema};
+    use crate::schemas::master::reason_code_schema::{InsertReasonCodeSchema, ReasonCodeCodeQuery, ReasonCodeDescriptionQuery, ReasonCodeSchema, UpdateReasonCodeSchema};
+    use crate::schemas::master::state_schema::{InsertStatesSchema, StateCodeQuery, StateNameQuery, StatesSchema, UpdateStatesSchema};
+    use crate::schemas::master::tree_schema::{CityNode, CountryNode, StateNode};
+
+    master_openapi!(
+        CountryApiDoc, country_paths, "Country",
+        row: CountrySchema, insert: InsertCountrySchema, update: UpdateCountrySchema,
+        code_query: CountryCodeQuery, name_query: CountryNameQuery,
+        list: "/precise/api/master/countries", item: "/precise/api/master/countries/{id}",
+        by_code: "/precise/api/master/countries/by-code/{code}", resolve: "/precise/api/master/countries/resolve",
+        export: "/precise/api/master/countries/export", import: "/precise/api/master/countries/import",
+        restore: "/precise/api/master/countries/{id}/restore", dependents: "/precise/api/master/countries/{id}/dependents",
+        history: "/precise/api/master/countries/{id}/history",
+        exists_code: "/precise/api/master/countries/exists/code", exists_name: "/precise/api/master/countries/exists/name"
+    );
+
+    master_openapi!(
+        StateApiDoc, state_paths, "State",
+        row: StatesSchema, insert: InsertStatesSchema, update: UpdateStatesSchema,
+        code_query: StateCodeQuery, name_query: StateNameQuery,
+        list: "/precise/api/master/states", item: "/precise/api/master/states/{id}",
+        by_code: "/precise/api/master/states/by-code/{code}", resolve: "/precise/api/master/states/resolve",
+        export: "/precise/api/master/states/export", import: "/precise/api/master/states/import",
+        restore: "/precise/api/master/states/{id}/restore", dependents: "/precise/api/master/states/{id}/dependents",
+        history: "/precise/api/master/states/{id}/history",
+        exists_code: "/precise/api/master/states/exists/code", exists_name: "/precise/api/master/states/exists/name"
+    );
+
+    master_openapi!(
+        CityApiDoc, city_paths, "City",
+        row: CitiesSchema, insert: InsertCitySchema, update: UpdateCitySchema,
+        code_query: CityCodeQuery, name_query: CityNameQuery,
+        list: "/precise/api/master/cities", item: "/precise/api/master/cities/{id}",
+        by_code: "/precise/api/master/cities/by-code/{code}", resolve: "/precise/api/master/cities/resolve",
+        export: "/precise/api/master/cities/export", import: "/precise/api/master/cities/import",
+        restore: "/precise/api/master/cities/{id}/restore", dependents: "/precise/api/master/cities/{id}/dependents",
+        history: "/precise/api/master/cities/{id}/history",
+        exists_code: "/precise/api/master/cities/exists/code", exists_name: "/precise/api/master/cities/exists/name"
+    );
+
+    master_openapi!(
+        ReasonCodeApiDoc, reason_code_paths, "Reason code",
+        row: ReasonCodeSchema, insert: InsertReasonCodeSchema, update: UpdateReasonCodeSchema,
+        code_query: ReasonCodeCodeQuery, name_query: ReasonCodeDescriptionQuery,
+        list: "/precise/api/master/reason-codes", item: "/precise/api/master/reason-codes/{id}",
+        by_code: "/precise/api/master/reason-codes/by-code/{code}", resolve: "/precise/api/master/reason-codes/resolve",
+        export: "/precise/api/master/reason-codes/export", import: "/precise/api/master/reason-codes/import",
+        restore: "/precise/api/master/reason-codes/{id}/restore", dependents: "/precise/api/master/reason-codes/{id}/dependents",
+        history: "/precise/api/master/reason-codes/{id}/history",
+        exists_code: "/precise/api/master/reason-codes/exists/code", exists_name: "/precise/api/master/reason-codes/exists/name"
+    );
+
+    pub mod hierarchy_paths {
+        #[allow(unused_imports)]
+        use crate::schemas::api_schemas::ApiResponse;
+        #[allow(unused_imports)]
+        use crate::schemas::master::{city_schema::CitiesSchema, list_schema::ListQueryParams, state_schema::StatesSchema, tree_schema::CountryNode};
+
+        #[utoipa::path(
+            get,
+            path = "/precise/api/master/tree",
+            tag = "Hierarchy",
+            responses(
+                (status = 200, description="Countries with their states and cities", body = ApiResponse<Vec<CountryNode>>),
+                (status = 500, description="Failed to retrieve data", body = ApiResponse<String>)
+            ),
+            security(("bearer_auth" = []))
+        )]
+        pub fn get_tree() {}
+
+        #[utoipa::path(
+            get,
+            path = "/precise/api/master/countries/{id}/states",
+            tag = "Hierarchy",
+            params(("id" = u32, Path, description = "Country id"), ListQueryParams),
+            responses(
+                (status = 200, description="States of the country", body = ApiResponse<Vec<StatesSchema>>),
+                (status = 404, description="Country not found", body = ApiResponse<String>)
+            ),
+            security(("bearer_auth" = []))
+        )]
+        pub fn get_country_states() {}
+
+        #[utoipa::path(
+            get,
+            path = "/precise/api/master/countries/{id}/cities",
+            tag = "Hierarchy",
+            params(("id" = u32, Path, description = "Country id"), ListQueryParams),
+            responses(
+                (status = 200, description="Cities of the country", body = ApiResponse<Vec<CitiesSchema>>),
+                (status = 404, description="Country not found", body = ApiResponse<String>)
+            ),
+            security(("bearer_auth" = []))
+        )]
+        pub fn get_country_cities() {}
+
+        #[utoipa::path(
+            get,
+            path = "/precise/api/master/states/{id}/cities",
+            tag = "Hierarchy",
+            params(("id" = u32, Path, description = "State id"), ListQueryParams),
+            responses(
+                (status = 200, description="Cities of the state", body = ApiResponse<Vec<CitiesSchema>>),
+                (status = 404, description="State not found", body = ApiResponse<String>)
+            ),
+            security(("bearer_auth" = []))
+        )]
+        pub fn get_state_cities() {}
+    }
+
+    /// OpenAPI Documentation for Precise
+    #[derive(utoipa::OpenApi)]
+    #[openapi(
+        modifiers(&SecurityAddon),
+        security(
+            ("bearerAuth"= [])
+        ),
+        paths(hierarchy_paths::get_tree, hierarchy_paths::get_country_states, hierarchy_paths::get_country_cities, hierarchy_paths::get_state_cities),
+        components(schemas(CountryNode, StateNode, CityNode, StatesSchema, CitiesSchema, Pagination)),
+        tags(
+            (name = "Precise API", description="API for Precise")
+        ),
+        info(
+            title = "Precise API Service",
+            version = "1.0.0",
+            license(
+                name = "MIT"
+            ),
+            contact(
+                name = "PT Presindo Central",
+                url = "https://www.onyxhouseware.com",
+                email = "smart.presindo@gmail.com"
+            ),
+            description = "Precise API for PT Presindo Central"
+        ),
+    )]
+    pub struct HierarchyApiDoc;
+
+    pub(crate) struct SecurityAddon;
+
+    impl Modify for SecurityAddon{
+        fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+            if let Some(components) = openapi.components.as_mut(){
+                components.add_security_scheme("bearer_auth",
+                    SecurityScheme::Http(utoipa::openapi::security::Http::new(HttpAuthScheme::Bearer))
+                );
+            }
+        }
+    }
+}
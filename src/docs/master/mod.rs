@@ -1,3 +1 @@
-pub mod country_openapi;
-pub mod state_openapi;
-pub mod city_openapi;
\ No newline at end of file
+pub mod master_openapi;
\ No newline at end of file
@@ -1,2 +1,3 @@
+pub mod audit;
 pub mod master;
 pub mod docs_routes;
\ No newline at end of file
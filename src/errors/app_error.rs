@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+use sqlx::error::ErrorKind;
+use validator::{ValidationErrors, ValidationErrorsKind};
+
+use crate::schemas::api_schemas::ApiResponse;
+
+/// Handler error rendered as an `ApiResponse`. Database and internal details are
+/// only written to the server log, clients get a generic message per status.
+#[derive(Debug)]
+pub enum AppError {
+    Validation(String),
+    /// Failed `validator` rules, rendered as a map of field to messages.
+    InvalidFields(ValidationErrors),
+    NotFound(String),
+    Conflict(String),
+    Unprocessable(String),
+    Internal(String),
+    Database(sqlx::Error),
+}
+
+impl AppError {
+    fn database_status(err: &sqlx::Error) -> StatusCode {
+        match err {
+            sqlx::Error::RowNotFound => StatusCode::NOT_FOUND,
+            sqlx::Error::Database(db_err) => match db_err.kind() {
+                ErrorKind::UniqueViolation => StatusCode::CONFLICT,
+                ErrorKind::ForeignKeyViolation => StatusCode::UNPROCESSABLE_ENTITY,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            },
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn client_message(&self) -> String {
+        match self {
+            AppError::Validation(message)
+            | AppError::NotFound(message)
+            | AppError::Conflict(message)
+            | AppError::Unprocessable(message) => message.clone(),
+            AppError::InvalidFields(_) => "Validation failed".into(),
+            AppError::Internal(_) => "internal server error".into(),
+            AppError::Database(err) => match Self::database_status(err) {
+                StatusCode::NOT_FOUND => "Data not found".into(),
+                StatusCode::CONFLICT => "Data already exists".into(),
+                StatusCode::UNPROCESSABLE_ENTITY => "Data is referenced by or references another record".into(),
+                _ => "Failed to process data".into(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::Database(err) => write!(f, "database error: {}", err),
+            AppError::Internal(message) => write!(f, "internal error: {}", message),
+            _ => f.write_str(&self.client_message()),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl ResponseError for AppError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            AppError::Validation(_) | AppError::InvalidFields(_) => StatusCode::BAD_REQUEST,
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::Unprocessable(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            AppError::Database(err) => Self::database_status(err),
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let status = self.status_code();
+
+        match self {
+            AppError::Database(err) if status.is_server_error() => log::error!("{:?}", err),
+            AppError::Database(err) => log::warn!("{:?}", err),
+            AppError::Internal(message) => log::error!("{}", message),
+            _ => {},
+        }
+
+        if let AppError::InvalidFields(errors) = self {
+            return ApiResponse{ status: status.as_u16(), message: self.client_message(), data: Some(field_errors(errors)), pagination: None }.to_http_response();
+        }
+
+        ApiResponse::<()>::error(status.as_u16(), &self.client_message()).to_http_response()
+    }
+}
+
+/// Messages per field as rendered in the `data` of a 400 response.
+pub fn field_errors(errors: &ValidationErrors) -> BTreeMap<String, Vec<String>> {
+    let mut fields = BTreeMap::new();
+    collect_field_errors("", errors, &mut fields);
+    fields
+}
+
+/// Flattens nested validation errors into `field`, `parent.field` and `list[0].field` keys.
+fn collect_field_errors(prefix: &str, errors: &ValidationErrors, fields: &mut BTreeMap<String, Vec<String>>) {
+    for (field, kind) in errors.errors() {
+        let path = if prefix.is_empty() { field.to_string() } else { format!("{}.{}", prefix, field) };
+
+        match kind {
+            ValidationErrorsKind::Field(field_errors) => {
+                fields.entry(path).or_default().extend(field_errors.iter().map(|error| {
+                    error.message.as_ref().map(|message| message.to_string()).unwrap_or_else(|| error.code.to_string())
+                }));
+            },
+            ValidationErrorsKind::Struct(nested) => collect_field_errors(&path, nested, fields),
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    collect_field_errors(&format!("{}[{}]", path, index), nested, fields);
+                }
+            },
+        }
+    }
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(err: sqlx::Error) -> Self {
+        AppError::Database(err)
+    }
+}
+
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        AppError::InvalidFields(errors)
+    }
+}
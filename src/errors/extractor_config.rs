@@ -0,0 +1,16 @@
+use actix_web::web;
+
+use crate::errors::AppError;
+
+/// Malformed JSON bodies answer with the `ApiResponse` 400 envelope instead of plain text.
+pub fn json_config() -> web::JsonConfig {
+    web::JsonConfig::default().error_handler(|err, _| AppError::Validation(format!("invalid JSON body: {}", err)).into())
+}
+
+pub fn query_config() -> web::QueryConfig {
+    web::QueryConfig::default().error_handler(|err, _| AppError::Validation(format!("invalid query string: {}", err)).into())
+}
+
+pub fn path_config() -> web::PathConfig {
+    web::PathConfig::default().error_handler(|err, _| AppError::Validation(format!("invalid path: {}", err)).into())
+}
@@ -0,0 +1,5 @@
+mod app_error;
+mod extractor_config;
+
+pub use app_error::*;
+pub use extractor_config::*;
\ No newline at end of file
@@ -0,0 +1,11 @@
+#![allow(clippy::module_inception)]
+
+pub mod modules;
+pub mod repositories;
+pub mod routes;
+pub mod schemas;
+pub mod settings;
+pub mod utils;
+pub mod docs;
+pub mod errors;
+pub mod middlewares;
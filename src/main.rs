@@ -1,27 +1,54 @@
-use std::env;
-
 use actix_web::middleware::from_fn;
 use actix_web::{web, App, HttpResponse, HttpServer, Responder};
-use docs::docs_routes::docs_routes::{docs_routes, openapi_routes};
-use routes::routes::routes::all_routes;
-use crate::middlewares::{logging_middleware::logging::log_middleware, JwtMiddleware};
-use crate::settings::db_pool;
-
-pub mod modules;
-pub mod routes;
-pub mod schemas;
-pub mod settings;
-pub mod utils;
-pub mod docs;
-pub mod middlewares;
+use rust_precise::errors::{json_config, path_config, query_config};
+use rust_precise::docs::docs_routes::docs_routes::{docs_routes, openapi_routes};
+use rust_precise::modules::health::probe::probe::AppInfo;
+use rust_precise::repositories::audit::audit_repository::audit_repository::audit_repository;
+use rust_precise::repositories::master::master_repository::master_repository::master_repository;
+use rust_precise::routes::health::health_routes::health_routes::health_routes;
+use rust_precise::routes::routes::routes::all_routes;
+use rust_precise::middlewares::{logging_middleware::logging::log_middleware, JwtMiddleware};
+use rust_precise::schemas::master::{city_schema::City, country_schema::Country, reason_code_schema::ReasonCode, state_schema::State};
+use rust_precise::settings::{connect_pool, get_configuration, migrate};
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    let pool = db_pool().await;
+    env_logger::init_from_env(env_logger::Env::default().default_filter_or("info"));
+    let settings = get_configuration().expect("Failed to read configuration");
+    let pool = connect_pool(&settings.database).await;
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("migrate") {
+        let dry_run = args.iter().any(|arg| arg == "--dry-run");
+        return migrate(&pool, dry_run).await.map_err(std::io::Error::other);
+    }
+
+    if settings.application.run_migrations {
+        migrate(&pool, false).await.map_err(std::io::Error::other)?;
+    }
+
+    let jwt_secret = web::Data::new(settings.jwt_secret.clone());
+    let app_info = web::Data::new(AppInfo::new(settings.environment));
+    let country_repository = master_repository::<Country>(pool.clone());
+    let state_repository = master_repository::<State>(pool.clone());
+    let city_repository = master_repository::<City>(pool.clone());
+    let reason_code_repository = master_repository::<ReasonCode>(pool.clone());
+    let audit_repository = audit_repository(pool.clone());
+    let reason_settings = web::Data::new(settings.reason.clone());
     HttpServer::new(move||{
-        let jwt_secret = env::var("JWT_SECRET").expect("No secret key in environment");
         App::new()
             .app_data(web::Data::new(pool.clone()))
+            .app_data(jwt_secret.clone())
+            .app_data(app_info.clone())
+            .app_data(country_repository.clone())
+            .app_data(state_repository.clone())
+            .app_data(city_repository.clone())
+            .app_data(reason_code_repository.clone())
+            .app_data(audit_repository.clone())
+            .app_data(reason_settings.clone())
+            .app_data(json_config())
+            .app_data(query_config())
+            .app_data(path_config())
             .wrap(from_fn(log_middleware))
             .service(
                 web::scope("/docs").configure(docs_routes)
@@ -29,15 +56,18 @@ async fn main() -> std::io::Result<()> {
             .service(
                 web::scope("/openapi").configure(openapi_routes)
             )
+            .service(
+                web::scope("/health").configure(health_routes)
+            )
             .service(
                 web::scope("/precise/api")
-                .wrap(JwtMiddleware::new(jwt_secret))
+                .wrap(JwtMiddleware::new(jwt_secret.clone()))
                 .configure(all_routes)
             )
             .default_service(web::to(not_found))
             
     })
-    .bind(("127.0.0.1", 8080))?
+    .bind(settings.application.address())?
     .run()
     .await
 }
@@ -1,9 +1,12 @@
 use std::{future::Future, pin::Pin};
 
-use actix_web::{dev::{Service, ServiceRequest, ServiceResponse, Transform}, error::ErrorUnauthorized, Error, HttpMessage};
+use actix_web::{dev::{Service, ServiceRequest, ServiceResponse, Transform}, error::ErrorUnauthorized, web, Error, HttpMessage};
 use futures::future::{ready, Ready};
 use jsonwebtoken::{decode, DecodingKey, Validation};
 use serde::{Deserialize, Serialize};
+
+use crate::settings::JwtSecret;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Claims{
     pub sub: String,
@@ -11,11 +14,11 @@ pub struct Claims{
 }
 
 pub struct JwtMiddleware {
-    jwt_secret: String,
+    jwt_secret: web::Data<JwtSecret>,
 }
 
 impl JwtMiddleware{
-    pub fn new(jwt_secret: String) -> Self{
+    pub fn new(jwt_secret: web::Data<JwtSecret>) -> Self{
         Self { jwt_secret }
     }
 }
@@ -44,7 +47,7 @@ where
 
 pub struct JwtMiddlewareService<S> {
     service: S,
-    jwt_secret: String,
+    jwt_secret: web::Data<JwtSecret>,
 }
 
 impl <S, B> Service<ServiceRequest> for JwtMiddlewareService<S>
@@ -66,7 +69,7 @@ where
     fn call(&self, req: ServiceRequest) -> Self::Future {
         if req.path() == "/precise/api/auth/login" {
             let fut = self.service.call(req);
-            return Box::pin(async move{ fut.await});
+            return Box::pin(fut);
         }
 
         let auth_header = req.headers()
@@ -81,7 +84,7 @@ where
                 Ok(token_data) => {
                     req.extensions_mut().insert(token_data.claims);
                     let fut = self.service.call(req);
-                    return Box::pin(async move{ fut.await});
+                    return Box::pin(fut);
                 }
                 Err(_) => {
                     return Box::pin(async move{
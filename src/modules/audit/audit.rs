@@ -0,0 +1,35 @@
+pub mod audit{
+    use actix_web::{web, HttpResponse};
+
+    use crate::errors::AppError;
+    use crate::repositories::audit::audit_repository::audit_repository::{audit_entity, AuditRepositoryData};
+    use crate::repositories::master::master_repository::master_repository::RepositoryData;
+    use crate::schemas::api_schemas::{ApiResponse, Pagination};
+    use crate::schemas::audit::audit_schema::{AuditFilter, AuditQuery, HistoryQuery};
+    use crate::schemas::master::master_entity::MasterEntity;
+
+    /// Changes across every audited table, filterable by user, entity and day range.
+    pub async fn get_audit((query, audit): (web::Query<AuditQuery>, AuditRepositoryData)) -> Result<HttpResponse, AppError> {
+        let filter = AuditFilter::try_from(query.into_inner()).map_err(AppError::Validation)?;
+        page(&filter, &audit).await
+    }
+
+    /// Changes of one record, newest first. Deleted records keep their history.
+    pub async fn get_history<E: MasterEntity>((path, query, audit, repository): (web::Path<E::Id>, web::Query<HistoryQuery>, AuditRepositoryData, RepositoryData<E>)) -> Result<HttpResponse, AppError> {
+        let id = path.into_inner();
+
+        if !repository.exists_id(id).await? {
+            return Err(AppError::NotFound(format!("{} ID not found", E::LABEL)));
+        }
+
+        let filter = query.into_inner().into_filter(audit_entity(E::TABLE), id.into()).map_err(AppError::Validation)?;
+        page(&filter, &audit).await
+    }
+
+    async fn page(filter: &AuditFilter, audit: &AuditRepositoryData) -> Result<HttpResponse, AppError> {
+        let (entries, total) = audit.find_page(filter).await?;
+
+        let pagination = Pagination{ total, per_page: filter.per_page, page: Some(filter.page), next_cursor: None };
+        Ok(ApiResponse::paginated("Data retrieved successfully", entries, pagination).to_http_response())
+    }
+}
@@ -1,16 +1,12 @@
-use std::env;
-
 use actix_web::{http::StatusCode, web, HttpResponse};
 use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
-use serde_json::Error;
 use sqlx::{MySqlPool, Row};
-use validator::Validate;
-use crate::{middlewares::Claims, schemas::auth::users_schema::UserLogin};
-use bcrypt::verify;
+use validator::{ValidationError, ValidationErrors};
+use crate::{errors::AppError, middlewares::Claims, schemas::auth::users_schema::UserLogin, settings::JwtSecret, utils::validated_json::ValidatedJson};
+use bcrypt::{hash, verify, BcryptError, DEFAULT_COST};
 
 
-async fn create_jwt(user_id: &str) -> String{
-    let secret = env::var("JWT_SECRET").expect("No secret key in environment");
+pub async fn create_jwt(user_id: &str, secret: &JwtSecret) -> String{
     let expiration = chrono::Utc::now()
     .checked_add_signed(chrono::Duration::seconds(480*60))
     .expect("Valid timestamp")
@@ -21,7 +17,38 @@ async fn create_jwt(user_id: &str) -> String{
         exp: expiration
     };
 
-    encode(&Header::new(Algorithm::default()), &claims, &EncodingKey::from_secret(secret.as_ref())).expect("Token cannot be created")
+    encode(&Header::new(Algorithm::default()), &claims, &EncodingKey::from_secret(secret.as_bytes())).expect("Token cannot be created")
+}
+
+pub fn hash_password(password: &str) -> Result<String, BcryptError> {
+    hash(password, DEFAULT_COST)
+}
+
+pub async fn user_exists(pool: &MySqlPool, user_id: &str) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar::<_, i64>("select count(*) from precise.users where user_id = ?")
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .map(|count| count > 0)
+}
+
+pub async fn insert_user(pool: &MySqlPool, user_id: &str, password_hash: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("insert into precise.users(user_id, password) values(?, ?)")
+        .bind(user_id)
+        .bind(password_hash)
+        .execute(pool)
+        .await
+        .map(|_| ())
+}
+
+/// Returns `false` when no user matched `user_id`.
+pub async fn update_password(pool: &MySqlPool, user_id: &str, password_hash: &str) -> Result<bool, sqlx::Error> {
+    sqlx::query("update precise.users set password = ? where user_id = ?")
+        .bind(password_hash)
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .map(|result| result.rows_affected() > 0)
 }
 
 pub async fn generate_test_jwt() -> String {
@@ -34,31 +61,33 @@ pub async fn generate_test_jwt() -> String {
         .unwrap()
 }
 
-pub async fn login((form, pool):(web::Json<UserLogin>, web::Data<MySqlPool>)) -> Result<HttpResponse, Error>{
+pub async fn login((form, pool, jwt_secret):(ValidatedJson<UserLogin>, web::Data<MySqlPool>, web::Data<JwtSecret>)) -> Result<HttpResponse, AppError>{
     let user = form.into_inner();
-
-    let validation = user.validate();
-
-    if validation.is_err(){
-        let err = user.validate().err().unwrap();
-        return Ok(HttpResponse::Ok().status(StatusCode::BAD_REQUEST).json(serde_json::json!({"status":"error","message": err})));
-    }
+    let (user_id, password) = match (user.user_id, user.password) {
+        (Some(user_id), Some(password)) => (user_id, password),
+        (user_id, _) => {
+            let field = if user_id.is_none() { "user_id" } else { "password" };
+            let mut errors = ValidationErrors::new();
+            errors.add(field, ValidationError::new("required"));
+            return Err(AppError::InvalidFields(errors));
+        },
+    };
 
     let check = sqlx::query("select password from precise.users where user_id = ?")
-        .bind(user.user_id.clone())
+        .bind(&user_id)
         .fetch_one(pool.get_ref())
         .await;
 
     match check{
         Ok(row) =>{
-            let db_pass = row.get("password");
+            let db_pass: String = row.get("password");
 
-            if verify(&user.password.unwrap(), db_pass).is_ok(){
-                let token = create_jwt(user.user_id.clone().unwrap().as_str()).await;
+            if matches!(verify(password, &db_pass), Ok(true)){
+                let token = create_jwt(&user_id, &jwt_secret).await;
                 let response = serde_json::json!({"status":"ok","message":"Success Login", "token": token});
                 Ok(HttpResponse::Ok().status(StatusCode::OK).json(response))
             }else{
-                let response = serde_json::json!({"status":"error","message":"Success Login"});
+                let response = serde_json::json!({"status":"error","message":"Invalid user id or password"});
                 Ok(HttpResponse::Unauthorized().status(StatusCode::UNAUTHORIZED).json(response))
             }
 
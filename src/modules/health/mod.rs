@@ -0,0 +1 @@
+pub mod probe;
\ No newline at end of file
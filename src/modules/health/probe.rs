@@ -0,0 +1,59 @@
+pub mod probe{
+    use std::time::Instant;
+
+    use actix_web::{web, HttpResponse};
+    use chrono::{DateTime, Utc};
+    use sqlx::MySqlPool;
+
+    use crate::schemas::{api_schemas::ApiResponse, health::health_schema::{BuildInfoSchema, LivenessSchema, ReadinessSchema}};
+    use crate::settings::Environment;
+
+    /// Process level facts reported by `/health/info`, registered once in `main`.
+    pub struct AppInfo{
+        pub environment: Environment,
+        pub started_on: DateTime<Utc>,
+        started_at: Instant
+    }
+
+    impl AppInfo{
+        pub fn new(environment: Environment) -> Self{
+            Self { environment, started_on: Utc::now(), started_at: Instant::now() }
+        }
+    }
+
+    pub async fn live() -> HttpResponse {
+        ApiResponse::success("Service is alive", LivenessSchema{ status: "up".into() }).to_http_response()
+    }
+
+    pub async fn ready(pool: web::Data<MySqlPool>) -> HttpResponse {
+        let database_up = sqlx::query("select 1")
+            .execute(pool.get_ref())
+            .await
+            .is_ok();
+
+        let readiness = ReadinessSchema{
+            status: if database_up { "up".into() } else { "down".into() },
+            database: if database_up { "up".into() } else { "down".into() },
+            pool_size: pool.size(),
+            idle_connections: pool.num_idle()
+        };
+
+        if database_up {
+            ApiResponse::success("Service is ready", readiness).to_http_response()
+        } else {
+            ApiResponse{ status: 503, message: "Database is unavailable".into(), data: Some(readiness), pagination: None }.to_http_response()
+        }
+    }
+
+    pub async fn info(app_info: web::Data<AppInfo>) -> HttpResponse {
+        let info = BuildInfoSchema{
+            version: env!("CARGO_PKG_VERSION").into(),
+            git_commit: env!("GIT_COMMIT").into(),
+            profile: app_info.environment.to_string(),
+            started_on: app_info.started_on.timestamp(),
+            uptime_seconds: app_info.started_at.elapsed().as_secs()
+        };
+
+        ApiResponse::success("Build info", info).to_http_response()
+    }
+}
@@ -0,0 +1,135 @@
+pub mod audit{
+    use serde_json::{Map, Value};
+    use sqlx::{types::Json, Error, MySqlConnection};
+
+    use crate::repositories::audit::audit_repository::audit_repository::audit_entity;
+    use crate::schemas::master::{dependent_schema::Dependent, master_entity::MasterEntity};
+
+    pub enum AuditAction {
+        Insert,
+        Update,
+        Delete,
+        Restore,
+    }
+
+    impl AuditAction {
+        pub fn as_str(&self) -> &'static str {
+            match self {
+                AuditAction::Insert => "insert",
+                AuditAction::Update => "update",
+                AuditAction::Delete => "delete",
+                AuditAction::Restore => "restore",
+            }
+        }
+    }
+
+    /// Table whose rows are audited and the columns kept in the before/after values.
+    pub struct AuditTarget {
+        pub table: &'static str,
+        pub id_column: &'static str,
+        pub columns: &'static [&'static str],
+    }
+
+    impl AuditTarget {
+        pub fn of<E: MasterEntity>() -> Self {
+            AuditTarget{ table: E::TABLE, id_column: E::ID_COLUMN, columns: E::AUDIT_COLUMNS }
+        }
+
+        pub fn of_dependent(dependent: &Dependent) -> Self {
+            AuditTarget{ table: dependent.table, id_column: dependent.id_column, columns: dependent.audit_columns }
+        }
+    }
+
+    /// One change to audit, `actor` and `reason` come from the request.
+    pub struct AuditRecord<'a> {
+        pub target: &'a AuditTarget,
+        pub id: u32,
+        pub action: AuditAction,
+        pub actor: Option<&'a str>,
+        pub reason: Option<&'a str>,
+        pub reason_code: Option<&'a str>,
+    }
+
+    /// The audited columns of a row as a JSON object, `None` when the row does not exist.
+    /// The row stays locked until the transaction ends.
+    pub async fn snapshot(connection: &mut MySqlConnection, target: &AuditTarget, id: u32) -> Result<Option<Value>, Error> {
+        let fields: Vec<String> = target.columns.iter().map(|column| format!("'{}', {}", column, column)).collect();
+        let sql = format!(
+            "select json_object({}) from {} where {} = ? for update",
+            fields.join(", "), target.table, target.id_column
+        );
+
+        sqlx::query_scalar::<_, Json<Value>>(&sql)
+            .bind(id)
+            .fetch_optional(connection)
+            .await
+            .map(|values| values.map(|Json(values)| values))
+    }
+
+    /// Writes the audit row of a change made on the same connection, so it commits or
+    /// rolls back with it. `old` is the snapshot taken before the change.
+    pub async fn write_audit(connection: &mut MySqlConnection, record: &AuditRecord<'_>, old: Option<Value>) -> Result<(), Error> {
+        let new = snapshot(&mut *connection, record.target, record.id).await?;
+        let changes = diff(old.as_ref(), new.as_ref());
+
+        sqlx::query("insert into precise.audit_trail (table_name, record_id, action, changed_by, reason, reason_code, old_values, new_values, changes)
+            values (?, ?, ?, ?, ?, ?, ?, ?, ?)")
+            .bind(audit_entity(record.target.table))
+            .bind(record.id)
+            .bind(record.action.as_str())
+            .bind(record.actor)
+            .bind(record.reason)
+            .bind(record.reason_code)
+            .bind(old.map(Json))
+            .bind(new.map(Json))
+            .bind(Json(changes))
+            .execute(connection)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Fields whose value differs, as `{"field": {"old": .., "new": ..}}`.
+    pub fn diff(old: Option<&Value>, new: Option<&Value>) -> Value {
+        let empty = Map::new();
+        let old = old.and_then(Value::as_object).unwrap_or(&empty);
+        let new = new.and_then(Value::as_object).unwrap_or(&empty);
+
+        let mut changes = Map::new();
+        for field in old.keys().chain(new.keys().filter(|field| !old.contains_key(*field))) {
+            let before = old.get(field).unwrap_or(&Value::Null);
+            let after = new.get(field).unwrap_or(&Value::Null);
+
+            if before != after {
+                changes.insert(field.clone(), serde_json::json!({ "old": before, "new": after }));
+            }
+        }
+        Value::Object(changes)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use serde_json::json;
+
+        use super::diff;
+
+        #[test]
+        fn diff_keeps_changed_fields_only() {
+            let old = json!({"country_code": "IDN", "country_name": "Indonesia", "deleted_on": null});
+            let new = json!({"country_code": "IDN", "country_name": "Republic of Indonesia", "deleted_on": "2025-01-01 00:00:00"});
+
+            assert_eq!(diff(Some(&old), Some(&new)), json!({
+                "country_name": {"old": "Indonesia", "new": "Republic of Indonesia"},
+                "deleted_on": {"old": null, "new": "2025-01-01 00:00:00"},
+            }));
+            assert_eq!(diff(Some(&old), Some(&old)), json!({}));
+        }
+
+        #[test]
+        fn diff_of_an_insert_or_a_missing_field() {
+            assert_eq!(diff(None, Some(&json!({"code": "A"}))), json!({"code": {"old": null, "new": "A"}}));
+            assert_eq!(diff(Some(&json!({"code": "A"})), Some(&json!({}))), json!({"code": {"old": "A", "new": null}}));
+            assert_eq!(diff(Some(&json!({"code": null})), Some(&json!({}))), json!({}));
+        }
+    }
+}
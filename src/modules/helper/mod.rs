@@ -1 +1,6 @@
-pub mod reason;
\ No newline at end of file
+pub mod audit;
+pub mod patch;
+pub mod reason;
+pub mod seed;
+pub mod sheet;
+pub mod xlsx;
\ No newline at end of file
@@ -0,0 +1,64 @@
+pub mod patch{
+    use serde_json::{Map, Value};
+
+    /// Applies `patch` to `target` following JSON merge patch (RFC 7396): objects are merged
+    /// recursively, `null` removes a member and any other value replaces it.
+    pub fn merge_patch(target: &mut Value, patch: &Value) {
+        let Value::Object(patch_fields) = patch else {
+            *target = patch.clone();
+            return;
+        };
+
+        if !target.is_object() {
+            *target = Value::Object(Map::new());
+        }
+
+        if let Value::Object(target_fields) = target {
+            for (key, value) in patch_fields {
+                if value.is_null() {
+                    target_fields.remove(key);
+                } else {
+                    merge_patch(target_fields.entry(key.clone()).or_insert(Value::Null), value);
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use serde_json::json;
+
+        use super::merge_patch;
+
+        fn merged(target: serde_json::Value, patch: serde_json::Value) -> serde_json::Value {
+            let mut target = target;
+            merge_patch(&mut target, &patch);
+            target
+        }
+
+        #[test]
+        fn follows_the_rfc_7396_examples() {
+            let cases = [
+                (json!({"a": "b"}), json!({"a": "c"}), json!({"a": "c"})),
+                (json!({"a": "b"}), json!({"b": "c"}), json!({"a": "b", "b": "c"})),
+                (json!({"a": "b"}), json!({"a": null}), json!({})),
+                (json!({"a": "b", "b": "c"}), json!({"a": null}), json!({"b": "c"})),
+                (json!({"a": ["b"]}), json!({"a": "c"}), json!({"a": "c"})),
+                (json!({"a": "c"}), json!({"a": ["b"]}), json!({"a": ["b"]})),
+                (json!({"a": {"b": "c"}}), json!({"a": {"b": "d", "c": null}}), json!({"a": {"b": "d"}})),
+                (json!({"a": [{"b": "c"}]}), json!({"a": [1]}), json!({"a": [1]})),
+                (json!(["a", "b"]), json!(["c", "d"]), json!(["c", "d"])),
+                (json!({"a": "b"}), json!(["c"]), json!(["c"])),
+                (json!({"a": "foo"}), json!(null), json!(null)),
+                (json!({"a": "foo"}), json!("bar"), json!("bar")),
+                (json!({"e": null}), json!({"a": 1}), json!({"e": null, "a": 1})),
+                (json!([1, 2]), json!({"a": "b", "c": null}), json!({"a": "b"})),
+                (json!({}), json!({"a": {"bb": {"ccc": null}}}), json!({"a": {"bb": {}}})),
+            ];
+
+            for (target, patch, expected) in cases {
+                assert_eq!(merged(target.clone(), patch.clone()), expected, "{} patched with {}", target, patch);
+            }
+        }
+    }
+}
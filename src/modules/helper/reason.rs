@@ -1,37 +1,45 @@
 pub mod reason{
-    use actix_web::web;
-    use serde_json::Value;
-    use sqlx::{MySqlPool, Error};
+    use std::borrow::Cow;
 
-    use crate::schemas::helper::reason_schema::ReasonRequestSchema;
+    use validator::{ValidationError, ValidationErrors};
 
-    pub enum KindTransaction {
-        Update,
-        Delete,
-    }
-
-    pub async fn update_reason((kind_transaction, value, pool): (KindTransaction, &Value, web::Data<MySqlPool>)) -> Result<(), Error> {
-        let reason_data: ReasonRequestSchema = serde_json::from_value(value.clone()).unwrap();
-        
-        match kind_transaction {
-            KindTransaction::Update => { 
-                let _data = sqlx::query("set @userName=?, @reason=?")
-                    .bind(reason_data.updated_by.clone())
-                    .bind(reason_data.reason.clone())
-                    .execute(pool.get_ref())
-                    .await?;
+    use crate::repositories::master::master_repository::master_repository::RepositoryData;
+    use crate::schemas::helper::reason_schema::{ReasonAction, ReasonRequestSchema};
+    use crate::schemas::master::reason_code_schema::ReasonCode;
+    use crate::settings::ReasonSettings;
 
-                Ok(())
+    /// Adds the reason rules to the field rule `errors`. A `reason_code` must be a live,
+    /// active catalog code for `action`, the free text is then optional. Without a code the
+    /// trimmed text must reach `min_length`, unless `require_code` refuses it altogether.
+    pub async fn check_reason(settings: &ReasonSettings, codes: &RepositoryData<ReasonCode>, action: ReasonAction, reason: &ReasonRequestSchema, mut errors: ValidationErrors) -> Result<ValidationErrors, sqlx::Error> {
+        match reason.code() {
+            Some(code) => {
+                let usable = match codes.find_by_code(code).await {
+                    Ok(row) => row.active && row.deleted_on.is_none() && row.action == action.as_str(),
+                    Err(sqlx::Error::RowNotFound) => false,
+                    Err(error) => return Err(error),
+                };
+                if !usable {
+                    errors.add("reason_code", reason_error("unknown", format!("{} is not an active {} reason code", code, action.as_str())));
+                }
+            },
+            None if settings.require_code => {
+                errors.add("reason_code", reason_error("required", "reason_code is required".into()));
+            },
+            None => match reason.text() {
+                None => errors.add("reason", reason_error("required", "You should provide a reason or a reason_code".into())),
+                Some(text) if text.chars().count() < settings.min_length => {
+                    errors.add("reason", reason_error("length", format!("reason must be at least {} characters", settings.min_length)));
+                },
+                Some(_) => {},
             },
-            KindTransaction::Delete => {
-                let _data = sqlx::query("set @userName=?, @reason=?")
-                    .bind(reason_data.deleted_by.clone())
-                    .bind(reason_data.reason.clone())
-                    .execute(pool.get_ref())
-                    .await?;
-
-                Ok(())
-             },
         }
+        Ok(errors)
+    }
+
+    fn reason_error(code: &'static str, message: String) -> ValidationError {
+        let mut error = ValidationError::new(code);
+        error.message = Some(Cow::Owned(message));
+        error
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,154 @@
+pub mod seed{
+    use std::{fmt, fs, path::Path};
+
+    use serde::de::DeserializeOwned;
+    use sqlx::MySqlPool;
+
+    use crate::modules::helper::sheet::sheet::{Sheet, SheetRow};
+    use crate::modules::master::import::import::{commit_import, plan_import};
+    use crate::repositories::master::master_repository::master_repository::{master_repository, RepositoryData};
+    use crate::schemas::helper::reason_schema::ReasonRequestSchema;
+    use crate::schemas::helper::seed_schema::{MasterSeed, SeedReport, SeedSummary};
+    use crate::schemas::master::import_schema::{ImportMode, ImportStatus};
+    use crate::schemas::master::{city_schema::City, country_schema::Country, state_schema::State};
+    use crate::schemas::master::master_entity::MasterEntity;
+
+    #[derive(Debug)]
+    pub enum SeedError {
+        Io(std::io::Error),
+        Json(serde_json::Error),
+        Csv(csv::Error),
+        Database(sqlx::Error),
+        UnsupportedFile(String),
+    }
+
+    impl fmt::Display for SeedError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                SeedError::Io(err) => write!(f, "cannot read seed file: {}", err),
+                SeedError::Json(err) => write!(f, "invalid json seed: {}", err),
+                SeedError::Csv(err) => write!(f, "invalid csv seed: {}", err),
+                SeedError::Database(err) => write!(f, "database error: {}", err),
+                SeedError::UnsupportedFile(message) => f.write_str(message),
+            }
+        }
+    }
+
+    impl std::error::Error for SeedError {}
+
+    impl From<std::io::Error> for SeedError {
+        fn from(err: std::io::Error) -> Self { SeedError::Io(err) }
+    }
+
+    impl From<serde_json::Error> for SeedError {
+        fn from(err: serde_json::Error) -> Self { SeedError::Json(err) }
+    }
+
+    impl From<csv::Error> for SeedError {
+        fn from(err: csv::Error) -> Self { SeedError::Csv(err) }
+    }
+
+    impl From<sqlx::Error> for SeedError {
+        fn from(err: sqlx::Error) -> Self { SeedError::Database(err) }
+    }
+
+    /// Reads a `.json` file holding `countries`/`states`/`cities` arrays, or a `.csv`
+    /// file whose name starts with the entity it holds, e.g. `cities_jawa.csv`.
+    pub fn read_seed_file(path: &Path) -> Result<MasterSeed, SeedError> {
+        let extension = path.extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default()
+            .to_lowercase();
+
+        match extension.as_str() {
+            "json" => Ok(serde_json::from_str(&fs::read_to_string(path)?)?),
+            "csv" => {
+                let stem = path.file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .unwrap_or_default()
+                    .to_lowercase();
+                let mut seed = MasterSeed::default();
+
+                if stem.starts_with("countr") {
+                    seed.countries = read_csv(path)?;
+                } else if stem.starts_with("state") {
+                    seed.states = read_csv(path)?;
+                } else if stem.starts_with("cit") {
+                    seed.cities = read_csv(path)?;
+                } else {
+                    return Err(SeedError::UnsupportedFile(format!(
+                        "{}: csv file name must start with countries, states or cities", path.display()
+                    )));
+                }
+                Ok(seed)
+            },
+            _ => Err(SeedError::UnsupportedFile(format!("{}: only .json and .csv seeds are supported", path.display()))),
+        }
+    }
+
+    fn read_csv<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>, SeedError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_path(path)?;
+
+        reader.deserialize()
+            .collect::<Result<Vec<T>, _>>()
+            .map_err(SeedError::from)
+    }
+
+    const SEED_REASON: &str = "master data seed";
+
+    /// Upserts every row by business code through the import checks, parents first so
+    /// children resolve their parent codes. Each entity is written in its own
+    /// transaction. Rows failing the checks, naming an unknown or deleted parent or
+    /// matching a deleted record are reported as rejected, unchanged rows as skipped.
+    pub async fn load_seed(pool: &MySqlPool, seed: &MasterSeed, actor: &str) -> Result<SeedReport, SeedError> {
+        let reason = ReasonRequestSchema{
+            reason: SEED_REASON.to_string(),
+            updated_by: Some(actor.to_string()),
+            deleted_by: None,
+            reason_code: None
+        };
+
+        let countries = seed_sheet(["country_code", "country_name"], seed.countries.iter()
+            .map(|row| [row.country_code.clone(), row.country_name.clone()]));
+        let states = seed_sheet(["state_code", "state_name", "country_code"], seed.states.iter()
+            .map(|row| [row.state_code.clone(), row.state_name.clone(), row.country_code.clone()]));
+        let cities = seed_sheet(["city_code", "city_name", "state_code"], seed.cities.iter()
+            .map(|row| [row.city_code.clone(), row.city_name.clone(), row.state_code.clone()]));
+
+        let countries = seed_entity(&master_repository::<Country>(pool.clone()), &countries, &reason).await?;
+        let states = seed_entity(&master_repository::<State>(pool.clone()), &states, &reason).await?;
+        let cities = seed_entity(&master_repository::<City>(pool.clone()), &cities, &reason).await?;
+        Ok(SeedReport{ countries, states, cities })
+    }
+
+    /// Seed rows laid out like an import file, row numbers counting from the first seed row.
+    fn seed_sheet<const N: usize>(headers: [&str; N], rows: impl Iterator<Item = [String; N]>) -> Sheet {
+        Sheet{
+            headers: headers.iter().map(|header| header.to_string()).collect(),
+            rows: rows.enumerate()
+                .map(|(index, cells)| SheetRow{ number: index as u32 + 1, cells: cells.to_vec() })
+                .collect(),
+        }
+    }
+
+    /// Plans and commits one entity, summarised the way `precise-admin seed` prints it.
+    pub async fn seed_entity<E: MasterEntity>(repository: &RepositoryData<E>, sheet: &Sheet, reason: &ReasonRequestSchema) -> Result<SeedSummary, sqlx::Error> {
+        let plan = plan_import(sheet, ImportMode::Commit, reason, repository).await?;
+        let report = commit_import(plan, reason, repository).await?;
+
+        Ok(SeedSummary{
+            inserted: report.inserted,
+            updated: report.updated,
+            skipped: report.unchanged,
+            rejected: report.rows.iter()
+                .filter(|row| row.status == ImportStatus::Rejected)
+                .map(|row| {
+                    let messages: Vec<String> = row.errors.values().flatten().cloned().collect();
+                    format!("{} {}: {}", E::LABEL.to_lowercase(), row.code, messages.join("; "))
+                })
+                .collect(),
+        })
+    }
+}
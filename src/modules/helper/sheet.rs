@@ -0,0 +1,449 @@
+pub mod sheet{
+    use std::borrow::Cow;
+    use std::io::{Cursor, Read};
+
+    use quick_xml::events::{BytesEnd, BytesStart, Event};
+    use quick_xml::Reader;
+    use zip::result::ZipError;
+    use zip::ZipArchive;
+
+    use crate::schemas::master::import_schema::MAX_IMPORT_ROWS;
+
+    /// Header and data rows of an uploaded CSV or XLSX file, every cell as trimmed text.
+    pub struct Sheet {
+        /// Lowercased names from the first row that is not blank.
+        pub headers: Vec<String>,
+        pub rows: Vec<SheetRow>,
+    }
+
+    pub struct SheetRow {
+        /// Row number as shown by a spreadsheet, the first line is row 1.
+        pub number: u32,
+        pub cells: Vec<String>,
+    }
+
+    impl SheetRow {
+        pub fn is_blank(&self) -> bool {
+            self.cells.iter().all(String::is_empty)
+        }
+    }
+
+    const ZIP_SIGNATURE: &[u8] = b"PK\x03\x04";
+    const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+    /// Largest unpacked workbook part read, far above the sheet of any accepted import.
+    const MAX_PART_BYTES: u64 = 32 * 1024 * 1024;
+
+    /// Columns of a worksheet, `A` to `XFD`.
+    const MAX_COLUMNS: usize = 16_384;
+
+    /// Cells kept from one worksheet, gaps included, far above any accepted import.
+    const MAX_CELLS: usize = 1_000_000;
+
+    /// Reads the first worksheet of an XLSX workbook, recognised by its zip signature,
+    /// and anything else as UTF-8 CSV. Blank rows are dropped.
+    pub fn read_sheet(bytes: &[u8]) -> Result<Sheet, String> {
+        let rows = if bytes.starts_with(ZIP_SIGNATURE) { read_xlsx(bytes)? } else { read_csv(bytes)? };
+        let mut rows = rows.into_iter().filter(|row| !row.is_blank());
+
+        let header = rows.next().ok_or("the file has no header row")?;
+        Ok(Sheet{
+            headers: header.cells.iter().map(|cell| cell.to_lowercase()).collect(),
+            rows: rows.collect(),
+        })
+    }
+
+    /// Row numbers are the line a record starts on, blank lines included.
+    fn read_csv(bytes: &[u8]) -> Result<Vec<SheetRow>, String> {
+        let bytes = bytes.strip_prefix(UTF8_BOM).unwrap_or(bytes);
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(bytes);
+
+        let (mut counted, mut line) = (0, 1);
+        reader.records()
+            .map(|record| {
+                let record = record.map_err(|error| format!("invalid csv: {}", error))?;
+                let mut start = record.position().map_or(counted, |position| position.byte() as usize).min(bytes.len());
+                while matches!(bytes.get(start), Some(b'\r' | b'\n')) {
+                    start += 1;
+                }
+
+                line += bytes[counted.min(start)..start].iter().filter(|byte| **byte == b'\n').count() as u32;
+                counted = counted.max(start);
+                Ok(SheetRow{ number: line, cells: record.iter().map(str::to_string).collect() })
+            })
+            .collect()
+    }
+
+    fn read_xlsx(bytes: &[u8]) -> Result<Vec<SheetRow>, String> {
+        let mut archive = ZipArchive::new(Cursor::new(bytes)).map_err(|error| format!("invalid xlsx: {}", error))?;
+
+        let shared = match archive_text(&mut archive, "xl/sharedStrings.xml")? {
+            Some(xml) => shared_strings(&xml)?,
+            None => Vec::new(),
+        };
+        let path = first_sheet_path(&mut archive)?;
+        let xml = archive_text(&mut archive, &path)?.ok_or_else(|| format!("invalid xlsx: {} is missing", path))?;
+
+        sheet_rows(&xml, &shared)
+    }
+
+    /// Text of the part `name`, refusing parts that unpack beyond `MAX_PART_BYTES`.
+    fn archive_text(archive: &mut ZipArchive<Cursor<&[u8]>>, name: &str) -> Result<Option<String>, String> {
+        let file = match archive.by_name(name) {
+            Ok(file) => file,
+            Err(ZipError::FileNotFound) => return Ok(None),
+            Err(error) => return Err(format!("invalid xlsx: {}", error)),
+        };
+        if file.size() > MAX_PART_BYTES {
+            return Err(format!("invalid xlsx: {} unpacks to more than {} bytes", name, MAX_PART_BYTES));
+        }
+
+        // The declared size may lie, so the read is capped as well.
+        let mut text = String::new();
+        file.take(MAX_PART_BYTES + 1).read_to_string(&mut text).map_err(|error| format!("invalid xlsx: {}: {}", name, error))?;
+        if text.len() as u64 > MAX_PART_BYTES {
+            return Err(format!("invalid xlsx: {} unpacks to more than {} bytes", name, MAX_PART_BYTES));
+        }
+        Ok(Some(text))
+    }
+
+    /// Follows the workbook relationships to the part holding the first sheet.
+    fn first_sheet_path(archive: &mut ZipArchive<Cursor<&[u8]>>) -> Result<String, String> {
+        let workbook = archive_text(archive, "xl/workbook.xml")?.ok_or("invalid xlsx: xl/workbook.xml is missing")?;
+        let relations = archive_text(archive, "xl/_rels/workbook.xml.rels")?.unwrap_or_default();
+
+        let mut id = None;
+        walk(&workbook, "xl/workbook.xml", |node| {
+            if let Node::Open{ element, .. } = node {
+                if id.is_none() && element.local_name().as_ref() == b"sheet" {
+                    id = attribute(&element, b"id");
+                }
+            }
+            Ok(())
+        })?;
+
+        let mut target = None;
+        if let Some(id) = id {
+            walk(&relations, "xl/_rels/workbook.xml.rels", |node| {
+                if let Node::Open{ element, .. } = node {
+                    if target.is_none() && element.local_name().as_ref() == b"Relationship" && attribute(&element, b"Id").as_deref() == Some(id.as_str()) {
+                        target = attribute(&element, b"Target");
+                    }
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(match target {
+            Some(target) if target.starts_with('/') => target.trim_start_matches('/').to_string(),
+            Some(target) => format!("xl/{}", target),
+            None => "xl/worksheets/sheet1.xml".to_string(),
+        })
+    }
+
+    /// Texts of `xl/sharedStrings.xml` by index, rich text runs joined and phonetic hints left out.
+    fn shared_strings(xml: &str) -> Result<Vec<String>, String> {
+        let mut strings = Vec::new();
+        let mut current = String::new();
+        let (mut in_text, mut in_phonetic) = (false, false);
+
+        walk(xml, "xl/sharedStrings.xml", |node| {
+            match node {
+                Node::Open{ element, empty } => match element.local_name().as_ref() {
+                    b"si" if empty => strings.push(String::new()),
+                    b"si" => current.clear(),
+                    b"rPh" => in_phonetic = !empty,
+                    b"t" => in_text = !empty,
+                    _ => {},
+                },
+                Node::Close(element) => match element.local_name().as_ref() {
+                    b"si" => strings.push(std::mem::take(&mut current)),
+                    b"rPh" => in_phonetic = false,
+                    b"t" => in_text = false,
+                    _ => {},
+                },
+                Node::Text(text) if in_text && !in_phonetic => current.push_str(&text),
+                Node::Text(_) => {},
+            }
+            Ok(())
+        })?;
+        Ok(strings)
+    }
+
+    /// Rows that are not blank. Stops once there are more than `MAX_IMPORT_ROWS` data rows or
+    /// `MAX_CELLS` cells, and drops cells beyond the header, so far away cells cost nothing.
+    fn sheet_rows(xml: &str, shared: &[String]) -> Result<Vec<SheetRow>, String> {
+        let mut rows: Vec<SheetRow> = Vec::new();
+        let mut row: Option<SheetRow> = None;
+        let mut previous: Option<u32> = None;
+        let mut kept = 0;
+        let mut cell: Option<(usize, String)> = None;
+        let mut value = String::new();
+        let mut in_value = false;
+
+        walk(xml, "worksheet", |node| {
+            match node {
+                Node::Open{ element, empty } => match element.local_name().as_ref() {
+                    b"row" => {
+                        let number = match attribute(&element, b"r").and_then(|number| number.parse().ok()) {
+                            Some(number) => number,
+                            None => previous.map_or(Some(1), |previous| previous.checked_add(1))
+                                .ok_or("invalid xlsx: a row follows the last possible row number")?,
+                        };
+                        previous = Some(number);
+                        if !empty {
+                            row = Some(SheetRow{ number, cells: Vec::new() });
+                        }
+                    },
+                    b"c" if !empty => {
+                        let column = match attribute(&element, b"r") {
+                            Some(reference) => column_index(&reference)?,
+                            None => None,
+                        };
+                        let column = column.unwrap_or_else(|| row.as_ref().map_or(0, |row| row.cells.len()));
+                        if column >= MAX_COLUMNS {
+                            return Err(format!("invalid xlsx: a row has more than {} columns", MAX_COLUMNS));
+                        }
+                        let beyond_header = rows.first().is_some_and(|header| column >= header.cells.len());
+                        cell = (!beyond_header).then(|| (column, attribute(&element, b"t").unwrap_or_default()));
+                        value.clear();
+                    },
+                    b"v" | b"t" => in_value = cell.is_some() && !empty,
+                    _ => {},
+                },
+                Node::Close(element) => match element.local_name().as_ref() {
+                    b"row" => {
+                        if let Some(row) = row.take().filter(|row| !row.is_blank()) {
+                            if rows.len() > MAX_IMPORT_ROWS {
+                                return Err(format!("the file has more than {} rows, at most {} are accepted", MAX_IMPORT_ROWS, MAX_IMPORT_ROWS));
+                            }
+                            rows.push(row);
+                        }
+                    },
+                    b"c" => {
+                        if let (Some(row), Some((column, kind))) = (row.as_mut(), cell.take()) {
+                            let text = match kind.as_str() {
+                                "s" => value.trim().parse::<usize>().ok().and_then(|index| shared.get(index)).cloned().unwrap_or_default(),
+                                "b" => if value.trim() == "1" { "true".into() } else { "false".into() },
+                                _ => std::mem::take(&mut value),
+                            };
+                            if row.cells.len() <= column {
+                                kept += column + 1 - row.cells.len();
+                                if kept > MAX_CELLS {
+                                    return Err(format!("invalid xlsx: the sheet has more than {} cells", MAX_CELLS));
+                                }
+                                row.cells.resize(column + 1, String::new());
+                            }
+                            row.cells[column] = text.trim().to_string();
+                        }
+                    },
+                    b"v" | b"t" => in_value = false,
+                    _ => {},
+                },
+                Node::Text(text) if in_value => value.push_str(&text),
+                Node::Text(_) => {},
+            }
+            Ok(())
+        })?;
+        Ok(rows)
+    }
+
+    /// Zero based column of a cell reference such as `B12`, `None` without letters.
+    fn column_index(reference: &str) -> Result<Option<usize>, String> {
+        let letters: Vec<u8> = reference.bytes().take_while(u8::is_ascii_alphabetic).collect();
+        if letters.is_empty() {
+            return Ok(None);
+        }
+
+        let number = letters.iter().try_fold(0usize, |index, letter| {
+            index.checked_mul(26)?.checked_add((letter.to_ascii_uppercase() - b'A') as usize + 1)
+        });
+        match number {
+            Some(number) if number <= MAX_COLUMNS => Ok(Some(number - 1)),
+            _ => Err(format!("invalid xlsx: cell {} is beyond column XFD", reference)),
+        }
+    }
+
+    /// Element and text events of a workbook part, declarations and comments left out.
+    enum Node<'a> {
+        Open{ element: BytesStart<'a>, empty: bool },
+        Close(BytesEnd<'a>),
+        Text(Cow<'a, str>),
+    }
+
+    /// Feeds the events of `xml` to `visit`, stopping at the first malformed markup or
+    /// the first error `visit` returns. `part` names the part in errors.
+    fn walk<'a>(xml: &'a str, part: &str, mut visit: impl FnMut(Node<'a>) -> Result<(), String>) -> Result<(), String> {
+        let mut reader = Reader::from_str(xml);
+        let invalid = |error: quick_xml::Error| format!("invalid xlsx: {}: {}", part, error);
+
+        loop {
+            let node = match reader.read_event().map_err(invalid)? {
+                Event::Start(element) => Node::Open{ element, empty: false },
+                Event::Empty(element) => Node::Open{ element, empty: true },
+                Event::End(element) => Node::Close(element),
+                Event::Text(text) => Node::Text(text.unescape().map_err(invalid)?),
+                Event::CData(data) => Node::Text(Cow::Owned(String::from_utf8_lossy(&data).into_owned())),
+                Event::Eof => return Ok(()),
+                _ => continue,
+            };
+            visit(node)?;
+        }
+    }
+
+    /// Unescaped value of the attribute whose local name is `wanted`.
+    fn attribute(element: &BytesStart, wanted: &[u8]) -> Option<String> {
+        element.attributes()
+            .flatten()
+            .find(|attribute| attribute.key.local_name().as_ref() == wanted)
+            .and_then(|attribute| attribute.unescape_value().ok())
+            .map(Cow::into_owned)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::io::{Cursor, Write};
+
+        use zip::write::SimpleFileOptions;
+        use zip::ZipWriter;
+
+        use super::*;
+
+        const WORKBOOK: &str = r#"<workbook xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Data" sheetId="1" r:id="rId7"/></sheets></workbook>"#;
+        const RELATIONS: &str = r#"<Relationships><Relationship Id="rId7" Target="worksheets/data.xml"/></Relationships>"#;
+
+        fn workbook(parts: &[(&str, &str)]) -> Vec<u8> {
+            let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+            for (name, content) in parts {
+                zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+                zip.write_all(content.as_bytes()).unwrap();
+            }
+            zip.finish().unwrap().into_inner()
+        }
+
+        fn cells(rows: &[SheetRow]) -> Vec<Vec<&str>> {
+            rows.iter().map(|row| row.cells.iter().map(String::as_str).collect()).collect()
+        }
+
+        #[test]
+        fn csv_rows_carry_their_line_number() {
+            let csv = "\u{feff}Code,Name\r\n\r\nIDN, Indonesia \r\n\"JPN\",\"Japan\nNippon\"\n,\n\nKOR,Korea";
+            let sheet = read_sheet(csv.as_bytes()).unwrap();
+            let rows: Vec<(u32, Vec<&str>)> = sheet.rows.iter().map(|row| (row.number, row.cells.iter().map(String::as_str).collect())).collect();
+
+            assert_eq!(sheet.headers, vec!["code", "name"]);
+            assert_eq!(rows, vec![(3, vec!["IDN", "Indonesia"]), (4, vec!["JPN", "Japan\nNippon"]), (8, vec!["KOR", "Korea"])]);
+        }
+
+        #[test]
+        fn csv_without_rows_has_no_header() {
+            assert_eq!(read_sheet(b"\n \n,,\n").err().unwrap(), "the file has no header row");
+        }
+
+        #[test]
+        fn shared_strings_join_runs_and_skip_phonetic_hints() {
+            let xml = r#"<sst><si><t>plain</t></si><si><r><t>Ja</t></r><r><rPr><b/></rPr><t>va</t></r><rPh sb="0" eb="1"><t>ジャワ</t></rPh></si><si/></sst>"#;
+
+            assert_eq!(shared_strings(xml).unwrap(), vec!["plain", "Java", ""]);
+        }
+
+        #[test]
+        fn sheet_rows_read_every_cell_type() {
+            let xml = r#"<worksheet><sheetData><row r="1"><c r="A1" t="s"><v>1</v></c><c r="B1" t="inlineStr"><is><t> inline </t></is></c><c r="C1"><v>42</v></c><c r="D1" t="b"><v>1</v></c></row></sheetData></worksheet>"#;
+            let shared = vec!["zero".to_string(), "one".to_string()];
+
+            assert_eq!(cells(&sheet_rows(xml, &shared).unwrap()), vec![vec!["one", "inline", "42", "true"]]);
+        }
+
+        #[test]
+        fn sheet_rows_keep_gaps_of_sparse_rows_and_cells() {
+            let xml = r#"<sheetData><row r="2"><c r="A2"><v>1</v></c><c r="C2"><v>3</v></c></row><row r="5"><c r="B5"><v>x</v></c></row><row><c><v>y</v></c></row></sheetData>"#;
+            let rows = sheet_rows(xml, &[]).unwrap();
+
+            assert_eq!(rows.iter().map(|row| row.number).collect::<Vec<_>>(), vec![2, 5, 6]);
+            assert_eq!(cells(&rows), vec![vec!["1", "", "3"], vec!["", "x"], vec!["y"]]);
+        }
+
+        #[test]
+        fn sheet_rows_decode_entities_and_skip_comments() {
+            let xml = r#"<?xml version="1.0"?><!-- generated --><sheetData><row r="1"><c r="A1" t="inlineStr"><is><t>R&amp;D &lt;&#x41;&#66;&gt;</t></is></c><!-- <c r="B1"><v>hidden</v></c> --></row></sheetData>"#;
+
+            assert_eq!(cells(&sheet_rows(xml, &[]).unwrap()), vec![vec!["R&D <AB>"]]);
+        }
+
+        #[test]
+        fn sheet_rows_reject_malformed_markup() {
+            assert!(sheet_rows(r#"<sheetData><row r="1"><c r="A1"><v>1</c></row></sheetData>"#, &[]).is_err());
+            assert!(sheet_rows(r#"<sheetData><row r="1"><c r="A1"><v>&bogus;</v></c></row></sheetData>"#, &[]).is_err());
+        }
+
+        #[test]
+        fn sheet_rows_drop_cells_beyond_the_header() {
+            let xml = r#"<sheetData><row r="1"><c r="XFD1"/></row><row r="2"><c r="A2"><v>code</v></c><c r="B2"><v>name</v></c></row><row r="3"><c r="A3"><v>IDN</v></c><c r="XFD3"><v>far</v></c></row></sheetData>"#;
+
+            assert_eq!(cells(&sheet_rows(xml, &[]).unwrap()), vec![vec!["code", "name"], vec!["IDN"]]);
+        }
+
+        #[test]
+        fn sheet_rows_stop_past_the_cell_limit() {
+            let header = r#"<row r="1"><c r="XFD1"><v>wide</v></c></row>"#;
+            let xml = format!("<sheetData>{}{}</sheetData>", header, r#"<row><c r="XFD2"><v>x</v></c></row>"#.repeat(MAX_CELLS / MAX_COLUMNS));
+
+            assert_eq!(sheet_rows(&xml, &[]).err().unwrap(), format!("invalid xlsx: the sheet has more than {} cells", MAX_CELLS));
+        }
+
+        #[test]
+        fn sheet_rows_stop_past_the_import_limit() {
+            let row = r#"<row><c><v>x</v></c></row>"#;
+            let fits = format!("<sheetData>{}</sheetData>", row.repeat(MAX_IMPORT_ROWS + 1));
+            let beyond = format!("<sheetData>{}</sheetData>", row.repeat(MAX_IMPORT_ROWS + 2));
+
+            assert_eq!(sheet_rows(&fits, &[]).unwrap().len(), MAX_IMPORT_ROWS + 1);
+            assert_eq!(sheet_rows(&beyond, &[]).err().unwrap(), format!("the file has more than {} rows, at most {} are accepted", MAX_IMPORT_ROWS, MAX_IMPORT_ROWS));
+        }
+
+        #[test]
+        fn sheet_rows_reject_a_row_after_the_last_row_number() {
+            let xml = r#"<sheetData><row r="4294967295"><c r="A4294967295"><v>1</v></c></row><row><c><v>2</v></c></row></sheetData>"#;
+
+            assert!(sheet_rows(xml, &[]).is_err());
+        }
+
+        #[test]
+        fn column_index_stops_at_xfd() {
+            assert_eq!(column_index("A1"), Ok(Some(0)));
+            assert_eq!(column_index("AA7"), Ok(Some(26)));
+            assert_eq!(column_index("xfd1"), Ok(Some(MAX_COLUMNS - 1)));
+            assert_eq!(column_index("12"), Ok(None));
+            assert!(column_index("XFE1").is_err());
+            assert!(column_index("ZZZZZZZZZZZZZZZZ1").is_err());
+        }
+
+        #[test]
+        fn read_sheet_follows_the_workbook_relationships() {
+            let bytes = workbook(&[
+                ("xl/workbook.xml", WORKBOOK),
+                ("xl/_rels/workbook.xml.rels", RELATIONS),
+                ("xl/sharedStrings.xml", "<sst><si><t>Code</t></si></sst>"),
+                ("xl/worksheets/data.xml", r#"<worksheet><sheetData><row r="1"><c r="A1" t="s"><v>0</v></c></row><row r="2"/><row r="3"><c r="A3"><v>IDN</v></c></row></sheetData></worksheet>"#),
+            ]);
+            let sheet = read_sheet(&bytes).unwrap();
+
+            assert_eq!(sheet.headers, vec!["code"]);
+            assert_eq!(sheet.rows.iter().map(|row| (row.number, row.cells.clone())).collect::<Vec<_>>(), vec![(3, vec!["IDN".to_string()])]);
+        }
+
+        #[test]
+        fn read_sheet_rejects_broken_workbooks() {
+            assert!(read_sheet(b"PK\x03\x04 not a zip").is_err());
+            assert!(read_sheet(&workbook(&[("xl/workbook.xml", WORKBOOK)])).is_err());
+
+            let wide = r#"<sheetData><row r="1"><c r="XFE1"><v>1</v></c></row></sheetData>"#;
+            assert!(read_sheet(&workbook(&[("xl/workbook.xml", "<workbook/>"), ("xl/worksheets/sheet1.xml", wide)])).is_err());
+        }
+    }
+}
@@ -0,0 +1,212 @@
+pub mod xlsx{
+    use std::io::{self, Write};
+    use std::sync::{Arc, Mutex};
+
+    use chrono::{Datelike, Timelike, Utc};
+    use serde_json::Value;
+    use zip::write::{SimpleFileOptions, StreamWriter};
+    use zip::{DateTime, ZipWriter};
+
+    const CONTENT_TYPES: &str = concat!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+        r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">"#,
+        r#"<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>"#,
+        r#"<Default Extension="xml" ContentType="application/xml"/>"#,
+        r#"<Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/>"#,
+        r#"<Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/>"#,
+        r#"</Types>"#,
+    );
+
+    const ROOT_RELS: &str = concat!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+        r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+        r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/>"#,
+        r#"</Relationships>"#,
+    );
+
+    const WORKBOOK_RELS: &str = concat!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+        r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+        r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/>"#,
+        r#"</Relationships>"#,
+    );
+
+    const SHEET_START: &str = concat!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+        r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#,
+    );
+
+    const SHEET_END: &str = "</sheetData></worksheet>";
+
+    /// Writes a one sheet workbook row by row. Finished bytes are handed out by `take`
+    /// rather than kept, so the workbook never sits in memory as a whole. Without zip64
+    /// the workbook stays under 4 GiB, far above any master list.
+    pub struct XlsxWriter {
+        zip: ZipWriter<StreamWriter<Output>>,
+        output: Output,
+        rows: u32,
+    }
+
+    impl XlsxWriter {
+        pub fn new(sheet_name: &str) -> io::Result<Self> {
+            let workbook = format!(
+                concat!(
+                    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+                    r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" "#,
+                    r#"xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">"#,
+                    r#"<sheets><sheet name="{}" sheetId="1" r:id="rId1"/></sheets></workbook>"#,
+                ),
+                escape(sheet_name)
+            );
+
+            let output = Output::default();
+            let mut zip = ZipWriter::new_stream(output.clone());
+            let options = SimpleFileOptions::default().last_modified_time(modified_now());
+            for (name, content) in [
+                ("[Content_Types].xml", CONTENT_TYPES),
+                ("_rels/.rels", ROOT_RELS),
+                ("xl/workbook.xml", &workbook),
+                ("xl/_rels/workbook.xml.rels", WORKBOOK_RELS),
+            ] {
+                zip.start_file(name, options)?;
+                zip.write_all(content.as_bytes())?;
+            }
+            zip.start_file("xl/worksheets/sheet1.xml", options)?;
+            zip.write_all(SHEET_START.as_bytes())?;
+            Ok(XlsxWriter{ zip, output, rows: 0 })
+        }
+
+        /// Appends the next row. Strings become inline text, numbers and booleans keep
+        /// their type, nulls leave the cell empty.
+        pub fn write_row(&mut self, cells: &[Value]) -> io::Result<()> {
+            self.rows += 1;
+            let mut xml = format!(r#"<row r="{}">"#, self.rows);
+
+            for (index, cell) in cells.iter().enumerate() {
+                let reference = format!("{}{}", column_name(index), self.rows);
+                match cell {
+                    Value::Null => continue,
+                    Value::Bool(flag) => xml.push_str(&format!(r#"<c r="{}" t="b"><v>{}</v></c>"#, reference, u8::from(*flag))),
+                    Value::Number(number) => xml.push_str(&format!(r#"<c r="{}"><v>{}</v></c>"#, reference, number)),
+                    Value::String(text) => xml.push_str(&inline_string(&reference, text)),
+                    other => xml.push_str(&inline_string(&reference, &other.to_string())),
+                }
+            }
+            xml.push_str("</row>");
+            self.zip.write_all(xml.as_bytes())
+        }
+
+        /// Bytes completed since the previous call.
+        pub fn take(&mut self) -> Vec<u8> {
+            self.output.take()
+        }
+
+        /// Closes the sheet and the archive, returning the remaining bytes.
+        pub fn finish(mut self) -> io::Result<Vec<u8>> {
+            self.zip.write_all(SHEET_END.as_bytes())?;
+            self.zip.finish()?;
+            Ok(self.output.take())
+        }
+    }
+
+    fn inline_string(reference: &str, text: &str) -> String {
+        format!(r#"<c r="{}" t="inlineStr"><is><t xml:space="preserve">{}</t></is></c>"#, reference, escape(text))
+    }
+
+    /// Letters of the zero based `index`, `A` to `Z` then `AA` and on.
+    fn column_name(index: usize) -> String {
+        let mut name = Vec::new();
+        let mut rest = index + 1;
+        while rest > 0 {
+            name.push(b'A' + ((rest - 1) % 26) as u8);
+            rest = (rest - 1) / 26;
+        }
+        name.iter().rev().map(|letter| *letter as char).collect()
+    }
+
+    /// Escapes markup and drops the control characters XML 1.0 cannot carry.
+    fn escape(text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len());
+        for character in text.chars() {
+            match character {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                '\t' | '\n' | '\r' => escaped.push(character),
+                control if control < ' ' => {},
+                _ => escaped.push(character),
+            }
+        }
+        escaped
+    }
+
+    /// Sink of the zip writer, shared with `XlsxWriter` to drain what it wrote.
+    #[derive(Clone, Default)]
+    struct Output(Arc<Mutex<Vec<u8>>>);
+
+    impl Output {
+        fn take(&self) -> Vec<u8> {
+            std::mem::take(&mut *self.0.lock().unwrap())
+        }
+    }
+
+    impl Write for Output {
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(bytes);
+            Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Current UTC time as a zip timestamp, the zip epoch when out of its range.
+    fn modified_now() -> DateTime {
+        let now = Utc::now();
+        u16::try_from(now.year()).ok()
+            .and_then(|year| DateTime::from_date_and_time(year, now.month() as u8, now.day() as u8, now.hour() as u8, now.minute() as u8, now.second() as u8).ok())
+            .unwrap_or_default()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use serde_json::json;
+
+        use super::*;
+        use crate::modules::helper::sheet::sheet::read_sheet;
+
+        #[test]
+        fn written_workbook_reads_back() {
+            let mut writer = XlsxWriter::new("countries & <more>").unwrap();
+            let mut bytes = writer.take();
+
+            writer.write_row(&[json!("country_code"), json!("country_name"), json!("active"), json!("rank")]).unwrap();
+            bytes.extend(writer.take());
+            writer.write_row(&[json!("IDN"), json!("Indonesia  "), json!(true), json!(1)]).unwrap();
+            writer.write_row(&[json!("JPN"), Value::Null, json!(false), json!(2.5)]).unwrap();
+            writer.write_row(&[json!("R&D <\u{1}>"), json!("日本"), Value::Null, json!({"nested": 1})]).unwrap();
+            bytes.extend(writer.take());
+            bytes.extend(writer.finish().unwrap());
+
+            let sheet = read_sheet(&bytes).unwrap();
+            let rows: Vec<(u32, Vec<&str>)> = sheet.rows.iter().map(|row| (row.number, row.cells.iter().map(String::as_str).collect())).collect();
+
+            assert_eq!(sheet.headers, vec!["country_code", "country_name", "active", "rank"]);
+            assert_eq!(rows, vec![
+                (2, vec!["IDN", "Indonesia", "true", "1"]),
+                (3, vec!["JPN", "", "false", "2.5"]),
+                (4, vec!["R&D <>", "日本", "", r#"{"nested":1}"#]),
+            ]);
+        }
+
+        #[test]
+        fn column_name_counts_like_a_spreadsheet() {
+            assert_eq!(column_name(0), "A");
+            assert_eq!(column_name(25), "Z");
+            assert_eq!(column_name(26), "AA");
+            assert_eq!(column_name(16_383), "XFD");
+        }
+    }
+}
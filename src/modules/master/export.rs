@@ -0,0 +1,154 @@
+pub mod export{
+    use std::collections::HashMap;
+    use std::io;
+
+    use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+    use actix_web::{web, HttpResponse};
+    use chrono::Utc;
+    use futures::stream::{self, BoxStream, Stream, StreamExt};
+    use serde::Serialize;
+    use serde_json::Value;
+
+    use crate::errors::AppError;
+    use crate::modules::helper::xlsx::xlsx::XlsxWriter;
+    use crate::repositories::master::master_repository::master_repository::RepositoryData;
+    use crate::schemas::master::export_schema::ExportFormat;
+    use crate::schemas::master::list_schema::ListQuery;
+    use crate::schemas::master::master_entity::MasterEntity;
+
+    /// Encoded rows are sent once a chunk reaches this size.
+    const CHUNK_BYTES: usize = 16 * 1024;
+
+    /// List parameters an export ignores, it always covers every matching row.
+    const PAGING_PARAMS: [&str; 3] = ["page", "per_page", "cursor"];
+
+    /// Lets Excel open the CSV as UTF-8, the import strips it again.
+    const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+    /// Streams every row matching the list filters, `q` and `sort` as CSV, NDJSON or XLSX,
+    /// encoding while the database is still being read. Columns follow the list response;
+    /// an export without rows has no header.
+    pub async fn export<E: MasterEntity>((query, repository): (web::Query<HashMap<String, String>>, RepositoryData<E>)) -> Result<HttpResponse, AppError> {
+        let mut params = query.into_inner();
+        let format = match params.remove("format") {
+            Some(format) => format.parse().map_err(AppError::Validation)?,
+            None => ExportFormat::default(),
+        };
+        params.retain(|key, _| !PAGING_PARAMS.contains(&key.as_str()));
+        let list_query = ListQuery::parse::<E>(&params).map_err(AppError::Validation)?;
+
+        let encoder = RowEncoder::new(format, E::PATH).map_err(|error| AppError::Internal(format!("{} export could not start: {}", E::LABEL, error)))?;
+        let filename = format!("{}-{}.{}", E::PATH, Utc::now().format("%Y%m%d"), format.extension());
+
+        Ok(HttpResponse::Ok()
+            .content_type(format.content_type())
+            .insert_header(ContentDisposition{
+                disposition: DispositionType::Attachment,
+                parameters: vec![DispositionParam::Filename(filename)],
+            })
+            .streaming(encoded_chunks::<E>(repository.export(&list_query), encoder)))
+    }
+
+    /// Once headers are sent a failure can only cut the body short, so it is logged here.
+    fn encoded_chunks<E: MasterEntity>(rows: BoxStream<'static, Result<E::Row, sqlx::Error>>, encoder: RowEncoder) -> impl Stream<Item = Result<web::Bytes, io::Error>> {
+        stream::unfold((rows, Some(encoder)), |(mut rows, encoder)| async move {
+            let mut encoder = encoder?;
+            let mut chunk = Vec::with_capacity(CHUNK_BYTES);
+
+            while chunk.len() < CHUNK_BYTES {
+                let encoded = match rows.next().await {
+                    Some(Ok(row)) => encoder.push(&row, &mut chunk),
+                    Some(Err(error)) => Err(io::Error::other(error)),
+                    None => {
+                        let finished = encoder.finish().map(|rest| {
+                            chunk.extend(rest);
+                            web::Bytes::from(chunk)
+                        });
+                        return Some((finished.inspect_err(|error| log_failure::<E>(error)), (rows, None)));
+                    },
+                };
+                if let Err(error) = encoded {
+                    log_failure::<E>(&error);
+                    return Some((Err(error), (rows, None)));
+                }
+            }
+            Some((Ok(web::Bytes::from(chunk)), (rows, Some(encoder))))
+        })
+    }
+
+    fn log_failure<E: MasterEntity>(error: &io::Error) {
+        log::error!("{} export aborted: {}", E::LABEL, error);
+    }
+
+    enum RowEncoder {
+        Csv{ header: bool },
+        Ndjson,
+        Xlsx{ writer: Box<XlsxWriter>, columns: Option<Vec<String>> },
+    }
+
+    impl RowEncoder {
+        fn new(format: ExportFormat, sheet_name: &str) -> io::Result<Self> {
+            Ok(match format {
+                ExportFormat::Csv => RowEncoder::Csv{ header: true },
+                ExportFormat::Ndjson => RowEncoder::Ndjson,
+                ExportFormat::Xlsx => RowEncoder::Xlsx{ writer: Box::new(XlsxWriter::new(sheet_name)?), columns: None },
+            })
+        }
+
+        /// Appends `row` to `out`, preceded by the header for the first one.
+        fn push<R: Serialize>(&mut self, row: &R, out: &mut Vec<u8>) -> io::Result<()> {
+            match self {
+                RowEncoder::Csv{ header } => {
+                    if *header {
+                        out.extend_from_slice(UTF8_BOM);
+                    }
+                    let mut writer = csv::WriterBuilder::new().has_headers(*header).from_writer(&mut *out);
+                    writer.serialize(row)?;
+                    writer.flush()?;
+                    *header = false;
+                },
+                RowEncoder::Ndjson => {
+                    serde_json::to_writer(&mut *out, row)?;
+                    out.push(b'\n');
+                },
+                RowEncoder::Xlsx{ writer, columns } => {
+                    let Value::Object(mut record) = serde_json::to_value(row)? else {
+                        return Err(io::Error::other("export rows must serialize as objects"));
+                    };
+                    let columns = match columns {
+                        Some(columns) => columns,
+                        None => {
+                            let names = field_names(row)?;
+                            writer.write_row(&names.iter().cloned().map(Value::String).collect::<Vec<_>>())?;
+                            columns.insert(names)
+                        },
+                    };
+                    let cells: Vec<Value> = columns.iter().map(|column| record.remove(column).unwrap_or(Value::Null)).collect();
+                    writer.write_row(&cells)?;
+                    out.extend(writer.take());
+                },
+            }
+            Ok(())
+        }
+
+        /// Bytes closing the file.
+        fn finish(self) -> io::Result<Vec<u8>> {
+            match self {
+                RowEncoder::Xlsx{ writer, .. } => writer.finish(),
+                RowEncoder::Csv{ .. } | RowEncoder::Ndjson => Ok(Vec::new()),
+            }
+        }
+    }
+
+    /// Field names in declaration order, which a `serde_json` map does not keep.
+    fn field_names<R: Serialize>(row: &R) -> io::Result<Vec<String>> {
+        let mut encoded = Vec::new();
+        {
+            let mut writer = csv::Writer::from_writer(&mut encoded);
+            writer.serialize(row)?;
+            writer.flush()?;
+        }
+        let mut reader = csv::Reader::from_reader(encoded.as_slice());
+        Ok(reader.headers()?.iter().map(str::to_string).collect())
+    }
+}
@@ -0,0 +1,350 @@
+pub mod import{
+    use std::borrow::Cow;
+    use std::collections::{BTreeMap, HashMap};
+
+    use actix_web::{web, HttpResponse};
+    use csv::StringRecord;
+    use serde::de::DeserializeOwned;
+    use serde_json::Value;
+    use validator::{Validate, ValidationError, ValidationErrors};
+
+    use crate::errors::{field_errors, AppError};
+    use crate::modules::helper::reason::reason::check_reason;
+    use crate::modules::helper::sheet::sheet::{read_sheet, Sheet, SheetRow};
+    use crate::repositories::master::master_repository::master_repository::{unique_checks, updated_record, RepositoryData};
+    use crate::repositories::master::validation_context::validation_context::unique_message;
+    use crate::schemas::api_schemas::ApiResponse;
+    use crate::schemas::helper::reason_schema::{ReasonAction, ReasonRequestSchema};
+    use crate::schemas::master::import_schema::{ImportChange, ImportMode, ImportQuery, ImportReportSchema, ImportRowSchema, ImportStatus, MAX_IMPORT_ROWS};
+    use crate::schemas::master::master_entity::{ImportParent, MasterEntity};
+    use crate::schemas::master::reason_code_schema::ReasonCode;
+    use crate::settings::ReasonSettings;
+
+    /// Upserts rows of a CSV or XLSX file by business code, parents named by code. Every
+    /// row is checked like a create or update and reported; `?mode=commit` writes the
+    /// valid rows in one transaction, the default dry run writes nothing.
+    pub async fn import<E: MasterEntity>((body, query, repository, reason_settings, reason_codes): (web::Bytes, web::Query<ImportQuery>, RepositoryData<E>, web::Data<ReasonSettings>, RepositoryData<ReasonCode>)) -> Result<HttpResponse, AppError> {
+        let query = query.into_inner();
+        let actor = query.imported_by.trim().to_string();
+
+        if actor.is_empty() {
+            return Err(AppError::Validation("imported_by is required".into()));
+        }
+
+        let sheet = read_sheet(&body).map_err(AppError::Validation)?;
+        if sheet.rows.len() > MAX_IMPORT_ROWS {
+            return Err(AppError::Validation(format!("the file has {} rows, at most {} are accepted", sheet.rows.len(), MAX_IMPORT_ROWS)));
+        }
+        if !sheet.headers.iter().any(|header| header == E::CODE_COLUMN) {
+            return Err(AppError::Validation(format!("missing column {}", E::CODE_COLUMN)));
+        }
+
+        let reason = ReasonRequestSchema{
+            reason: query.reason.unwrap_or_default(),
+            updated_by: Some(actor.clone()),
+            deleted_by: None,
+            reason_code: query.reason_code
+        };
+        let plan = plan_import(&sheet, query.mode, &reason, &repository).await?;
+
+        if plan.report.updated > 0 {
+            let errors = check_reason(&reason_settings, &reason_codes, ReasonAction::Update, &reason, ValidationErrors::new()).await?;
+            if !errors.is_empty() {
+                return Err(errors.into());
+            }
+        }
+
+        let report = match query.mode {
+            ImportMode::DryRun => plan.report,
+            ImportMode::Commit => commit_import(plan, &reason, &repository).await?,
+        };
+
+        let message = match query.mode {
+            ImportMode::DryRun => "Import checked successfully",
+            ImportMode::Commit => "Data imported successfully",
+        };
+        Ok(ApiResponse::success(message, report).to_http_response())
+    }
+
+    /// Checks every row of `sheet` like a create or update of `E` and queues the valid
+    /// changes without writing them. Shared by the import endpoint and the seed loader.
+    pub async fn plan_import<E: MasterEntity>(sheet: &Sheet, mode: ImportMode, reason: &ReasonRequestSchema, repository: &RepositoryData<E>) -> Result<ImportPlan<E>, sqlx::Error> {
+        let parents = resolve_parents::<E>(sheet, repository).await?;
+
+        let mut plan = ImportPlan::<E>{ report: ImportReportSchema::new(mode), changes: Vec::new(), changed_rows: Vec::new(), accepted: Vec::new() };
+        for row in &sheet.rows {
+            plan_row(&mut plan, &sheet.headers, row, &parents, reason, repository).await?;
+        }
+        Ok(plan)
+    }
+
+    /// Writes the queued changes of `plan` in one transaction and reports the stored ids.
+    pub async fn commit_import<E: MasterEntity>(plan: ImportPlan<E>, reason: &ReasonRequestSchema, repository: &RepositoryData<E>) -> Result<ImportReportSchema, sqlx::Error> {
+        let mut report = plan.report;
+
+        if !plan.changes.is_empty() {
+            let ids = repository.import(&plan.changes, reason).await?;
+
+            for (index, id) in plan.changed_rows.iter().zip(ids) {
+                report.rows[*index].id = Some(id.into());
+            }
+            report.committed = true;
+        }
+        Ok(report)
+    }
+
+    pub struct ImportPlan<E: MasterEntity> {
+        pub report: ImportReportSchema,
+        changes: Vec<ImportChange<E>>,
+        /// Index in `report.rows` of each change.
+        changed_rows: Vec<usize>,
+        /// Row number and resulting record of the accepted rows, for duplicates within the file.
+        accepted: Vec<(u32, Value)>,
+    }
+
+    /// Parent code column of the sheet with the live parent ids by lowercased code.
+    struct ResolvedParent {
+        parent: ImportParent,
+        ids: HashMap<String, u32>,
+    }
+
+    async fn resolve_parents<E: MasterEntity>(sheet: &Sheet, repository: &RepositoryData<E>) -> Result<Vec<ResolvedParent>, sqlx::Error> {
+        let mut resolved = Vec::new();
+
+        for parent in E::import_parents() {
+            let Some(column) = sheet.headers.iter().position(|header| header == parent.code_field) else {
+                continue;
+            };
+
+            let mut codes: Vec<String> = sheet.rows.iter()
+                .filter_map(|row| row.cells.get(column))
+                .filter(|code| !code.is_empty())
+                .cloned()
+                .collect();
+            codes.sort();
+            codes.dedup();
+
+            let ids = repository.resolve_parent(&parent, &codes).await?
+                .into_iter()
+                .map(|(code, id)| (code.to_lowercase(), id))
+                .collect();
+            resolved.push(ResolvedParent{ parent, ids });
+        }
+        Ok(resolved)
+    }
+
+    /// Outcome of a row that deserialized: its checks, the record it leaves behind and the change to write.
+    struct Planned<E: MasterEntity> {
+        status: ImportStatus,
+        id: Option<E::Id>,
+        errors: ValidationErrors,
+        record: Value,
+        change: Option<ImportChange<E>>,
+    }
+
+    /// Messages per column of a row rejected before its checks could run.
+    type Failure = BTreeMap<String, Vec<String>>;
+
+    /// Decides whether `row` inserts, updates, changes nothing or is rejected, and queues its change.
+    async fn plan_row<E: MasterEntity>(plan: &mut ImportPlan<E>, headers: &[String], row: &SheetRow, parents: &[ResolvedParent], reason: &ReasonRequestSchema, repository: &RepositoryData<E>) -> Result<(), sqlx::Error> {
+        let mut record = SheetRecord::new(headers, &row.cells);
+        let code = record.get(E::CODE_COLUMN).to_string();
+
+        let mut parent_errors = Vec::new();
+        for resolved in parents {
+            let parent_code = record.get(resolved.parent.code_field).to_string();
+            if parent_code.is_empty() {
+                continue;
+            }
+
+            match resolved.ids.get(&parent_code.to_lowercase()) {
+                Some(id) => record.set(resolved.parent.id_field, id.to_string()),
+                None => parent_errors.push(ParentError{
+                    code_field: resolved.parent.code_field,
+                    id_field: resolved.parent.id_field,
+                    message: format!("{} {} does not exist", resolved.parent.label, parent_code),
+                }),
+            }
+        }
+
+        let stored = match repository.find_by_code(&code).await {
+            Ok(stored) => Some(stored),
+            Err(sqlx::Error::RowNotFound) => None,
+            Err(error) => return Err(error),
+        };
+
+        let outcome = match stored {
+            None => plan_insert(&mut record, parents, &parent_errors, reason, repository).await?,
+            Some(stored) if E::row_deleted(&stored) => {
+                Err(Failure::from([(E::CODE_COLUMN.to_string(), vec![format!("{} {} is deleted, restore it first", E::LABEL, code)])]))
+            },
+            Some(stored) => plan_update(&mut record, &stored, parents, &parent_errors, reason, repository).await?,
+        };
+
+        let mut planned = match outcome {
+            Ok(planned) => planned,
+            Err(errors) => {
+                plan.report.push(ImportRowSchema{ row: row.number, code, status: ImportStatus::Rejected, id: None, errors });
+                return Ok(());
+            },
+        };
+
+        for check in unique_checks::<E>(&planned.record) {
+            if planned.errors.errors().contains_key(check.field) {
+                continue;
+            }
+            if let Some((number, _)) = plan.accepted.iter().find(|(_, accepted)| check.matches(accepted)) {
+                planned.errors.add(check.field, import_error("unique", format!("{} in row {}", unique_message(&check), number)));
+            }
+        }
+
+        let id = planned.id.map(Into::into);
+        if !planned.errors.is_empty() {
+            plan.report.push(ImportRowSchema{ row: row.number, code, status: ImportStatus::Rejected, id, errors: field_errors(&planned.errors) });
+            return Ok(());
+        }
+
+        plan.accepted.push((row.number, planned.record));
+        if let Some(change) = planned.change {
+            plan.changed_rows.push(plan.report.rows.len());
+            plan.changes.push(change);
+        }
+        plan.report.push(ImportRowSchema{ row: row.number, code, status: planned.status, id, errors: BTreeMap::new() });
+        Ok(())
+    }
+
+    async fn plan_insert<E: MasterEntity>(record: &mut SheetRecord, parents: &[ResolvedParent], parent_errors: &[ParentError], reason: &ReasonRequestSchema, repository: &RepositoryData<E>) -> Result<Result<Planned<E>, Failure>, sqlx::Error> {
+        record.set("created_by", reason.updated_by.clone().unwrap_or_default());
+
+        let data: E::Insert = match record.deserialize(parent_errors) {
+            Ok(data) => data,
+            Err(failure) => return Ok(Err(failure)),
+        };
+
+        let errors = with_parent_errors(data.validate().err().unwrap_or_default(), parents, parent_errors);
+        let errors = repository.validate_insert(&data, errors).await?;
+        let record = serde_json::to_value(&data).unwrap_or(Value::Null);
+
+        Ok(Ok(Planned{ status: ImportStatus::Insert, id: None, errors, record, change: Some(ImportChange::Insert(data)) }))
+    }
+
+    /// A row whose code is stored. Rows matching the stored values are `unchanged`, parents
+    /// the update schema does not carry cannot be changed.
+    async fn plan_update<E: MasterEntity>(record: &mut SheetRecord, stored: &E::Row, parents: &[ResolvedParent], parent_errors: &[ParentError], reason: &ReasonRequestSchema, repository: &RepositoryData<E>) -> Result<Result<Planned<E>, Failure>, sqlx::Error> {
+        let id = E::row_id(stored);
+        record.set("updated_by", reason.updated_by.clone().unwrap_or_default());
+        record.set("reason", reason.reason.clone());
+        record.set("reason_code", reason.reason_code.clone().unwrap_or_default());
+
+        let data: E::Update = match record.deserialize(parent_errors) {
+            Ok(data) => data,
+            Err(failure) => return Ok(Err(failure)),
+        };
+
+        let mut errors = with_parent_errors(data.validate().err().unwrap_or_default(), parents, parent_errors);
+        let current = serde_json::to_value(stored).unwrap_or(Value::Null);
+        let changes = serde_json::to_value(&data).unwrap_or(Value::Null);
+
+        for resolved in parents {
+            let field = resolved.parent.id_field;
+            let moved = record.get(field).parse::<u64>().is_ok_and(|parent_id| current[field].as_u64() != Some(parent_id));
+
+            if moved && changes.get(field).is_none() {
+                errors.add(resolved.parent.code_field, import_error("immutable", format!("{} of a stored {} cannot be changed", resolved.parent.code_field, E::LABEL)));
+            }
+        }
+
+        let errors = repository.validate_update(id, &data, errors).await?;
+        let unchanged = E::AUDIT_COLUMNS.iter().all(|column| changes.get(*column).is_none_or(|value| current[*column] == *value));
+        let record = updated_record::<E>(stored, &data);
+
+        Ok(Ok(if unchanged {
+            Planned{ status: ImportStatus::Unchanged, id: Some(id), errors, record, change: None }
+        } else {
+            Planned{ status: ImportStatus::Update, id: Some(id), errors, record, change: Some(ImportChange::Update(id, data)) }
+        }))
+    }
+
+    /// Unknown parent code, reported on the code column in place of the missing id.
+    struct ParentError {
+        code_field: &'static str,
+        id_field: &'static str,
+        message: String,
+    }
+
+    /// Reports parent problems on the code column of the sheet rather than on the id it resolves to.
+    fn with_parent_errors(mut errors: ValidationErrors, parents: &[ResolvedParent], parent_errors: &[ParentError]) -> ValidationErrors {
+        for resolved in parents {
+            if errors.errors_mut().remove(resolved.parent.id_field).is_some() && !parent_errors.iter().any(|error| error.id_field == resolved.parent.id_field) {
+                errors.add(resolved.parent.code_field, import_error("required", format!("{} is required", resolved.parent.code_field)));
+            }
+        }
+        for parent_error in parent_errors {
+            errors.add(parent_error.code_field, import_error("unknown", parent_error.message.clone()));
+        }
+        errors
+    }
+
+    fn import_error(code: &'static str, message: String) -> ValidationError {
+        let mut error = ValidationError::new(code);
+        error.message = Some(Cow::Owned(message));
+        error
+    }
+
+    /// One sheet row by column name, with the columns the import fills in itself.
+    struct SheetRecord {
+        headers: Vec<String>,
+        cells: Vec<String>,
+    }
+
+    impl SheetRecord {
+        fn new(headers: &[String], cells: &[String]) -> Self {
+            let mut cells = cells.to_vec();
+            cells.resize(headers.len(), String::new());
+            SheetRecord{ headers: headers.to_vec(), cells }
+        }
+
+        fn get(&self, column: &str) -> &str {
+            self.headers.iter().position(|header| header == column).map_or("", |index| &self.cells[index])
+        }
+
+        /// Replaces the value of `column`, adding the column when the sheet lacks it.
+        fn set(&mut self, column: &str, value: String) {
+            match self.headers.iter().position(|header| header == column) {
+                Some(index) => self.cells[index] = value,
+                None => {
+                    self.headers.push(column.to_string());
+                    self.cells.push(value);
+                },
+            }
+        }
+
+        /// Parses the cells into `T` the way CSV fields are read, so `true`, numbers and
+        /// enum names convert. A missing id whose parent code is unknown is not reported twice.
+        fn deserialize<T: DeserializeOwned>(&self, parent_errors: &[ParentError]) -> Result<T, Failure> {
+            StringRecord::from(self.cells.clone())
+                .deserialize(Some(&StringRecord::from(self.headers.clone())))
+                .map_err(|error| {
+                    let (column, message) = match error.kind() {
+                        csv::ErrorKind::Deserialize{ err, .. } => {
+                            let message = err.kind().to_string();
+                            let column = err.field().and_then(|index| self.headers.get(index as usize).cloned())
+                                .or_else(|| message.strip_prefix("missing field `").and_then(|rest| rest.strip_suffix('`')).map(str::to_string));
+                            (column, message)
+                        },
+                        _ => (None, error.to_string()),
+                    };
+
+                    let mut failure = Failure::new();
+                    for parent_error in parent_errors {
+                        failure.entry(parent_error.code_field.to_string()).or_default().push(parent_error.message.clone());
+                    }
+                    let column = column.unwrap_or_else(|| "row".to_string());
+                    if !parent_errors.iter().any(|parent_error| parent_error.id_field == column) {
+                        failure.entry(column).or_default().push(message);
+                    }
+                    failure
+                })
+        }
+    }
+}
@@ -0,0 +1,280 @@
+pub mod master{
+    use std::collections::HashMap;
+
+    use actix_web::{web, HttpResponse};
+    use serde_json::Value;
+    use validator::{Validate, ValidationErrors};
+
+    use crate::errors::AppError;
+    use crate::modules::helper::patch::patch::merge_patch;
+    use crate::modules::helper::reason::reason::check_reason;
+    use crate::repositories::master::master_repository::master_repository::RepositoryData;
+    use crate::schemas::api_schemas::{ApiResponse, Pagination};
+    use crate::schemas::helper::reason_schema::{DeleteReasonSchema, ReasonAction, ReasonRequestSchema, RestoreReasonSchema};
+    use crate::schemas::master::dependent_schema::{DeleteOutcome, DeleteQuery, RestoreOutcome};
+    use crate::schemas::master::list_schema::ListQuery;
+    use crate::schemas::master::master_entity::MasterEntity;
+    use crate::schemas::master::reason_code_schema::ReasonCode;
+    use crate::schemas::master::resolve_schema::{ResolveCodesSchema, ResolvedCodesSchema};
+    use crate::schemas::master::unique_schema::UniqueCheck;
+    use crate::settings::ReasonSettings;
+    use crate::utils::validated_json::ValidatedJson;
+
+    type QueryParams = web::Query<HashMap<String, String>>;
+    type ReasonData = web::Data<ReasonSettings>;
+    type ReasonCodes = RepositoryData<ReasonCode>;
+    type IdPath<E> = web::Path<<E as MasterEntity>::Id>;
+
+    /// Row members a patch cannot carry over from the stored record.
+    const AUDIT_FIELDS: [&str; 6] = ["created_on", "created_by", "updated_on", "updated_by", "deleted_on", "deleted_by"];
+
+    /// State reported for changes to a soft deleted record.
+    const DELETED: &str = "is deleted, restore it first";
+
+    /// Pages with `?page=&per_page=` or `?cursor=`, sorts and filters on the entity whitelist.
+    pub async fn get_all<E: MasterEntity>((query, repository): (QueryParams, RepositoryData<E>)) -> Result<HttpResponse, AppError> {
+        let list_query = ListQuery::parse::<E>(&query).map_err(AppError::Validation)?;
+        list(&list_query, &repository).await
+    }
+
+    /// Lists the `C` rows under parent `P`, e.g. the states of a country. Accepts the same
+    /// parameters as `get_all`, the parent filter always wins.
+    pub async fn get_nested<P: MasterEntity, C: MasterEntity>((path, query, parents, repository): (web::Path<P::Id>, QueryParams, RepositoryData<P>, RepositoryData<C>)) -> Result<HttpResponse, AppError> {
+        let parent_id = path.into_inner();
+
+        if !parents.exists_id(parent_id).await? {
+            return Err(AppError::NotFound(format!("{} ID not found", P::LABEL)));
+        }
+
+        let mut list_query = ListQuery::parse::<C>(&query).map_err(AppError::Validation)?;
+        list_query.filter_by::<C>(P::ID_COLUMN, parent_id.to_string()).map_err(AppError::Internal)?;
+        list(&list_query, &repository).await
+    }
+
+    async fn list<E: MasterEntity>(list_query: &ListQuery, repository: &RepositoryData<E>) -> Result<HttpResponse, AppError> {
+        let (result, total) = repository.find_page(list_query).await?;
+
+        let pagination = Pagination{
+            total,
+            per_page: list_query.per_page,
+            page: list_query.cursor.is_none().then_some(list_query.page),
+            next_cursor: match (list_query.cursor, result.last()) {
+                (Some(_), Some(last)) if result.len() == list_query.per_page as usize => Some(E::row_id(last).into()),
+                _ => None,
+            }
+        };
+        Ok(ApiResponse::paginated("Data retrieved successfully", result, pagination).to_http_response())
+    }
+
+    pub async fn get_by_id<E: MasterEntity>((path, repository): (IdPath<E>, RepositoryData<E>)) -> Result<HttpResponse, AppError> {
+        let result = repository.find_by_id(path.into_inner()).await?;
+        Ok(ApiResponse::success("Data retrieved successfully", result).to_http_response())
+    }
+
+    /// Same exact code match as `exists/code`.
+    pub async fn get_by_code<E: MasterEntity>((path, repository): (web::Path<String>, RepositoryData<E>)) -> Result<HttpResponse, AppError> {
+        let result = repository.find_by_code(&path.into_inner()).await?;
+        Ok(ApiResponse::success("Data retrieved successfully", result).to_http_response())
+    }
+
+    /// Maps business codes to ids. Codes match the way `exists/code` does, so with the
+    /// default collation `idn` resolves to the row coded `IDN`.
+    pub async fn resolve<E: MasterEntity>((form, repository): (ValidatedJson<ResolveCodesSchema>, RepositoryData<E>)) -> Result<HttpResponse, AppError> {
+        let request = form.into_inner();
+
+        let found: HashMap<String, u32> = repository.resolve_codes(&request.codes).await?
+            .into_iter()
+            .map(|(code, id)| (code.to_lowercase(), id.into()))
+            .collect();
+
+        let mut result = ResolvedCodesSchema{ ids: Default::default(), missing: Vec::new() };
+        for code in request.codes {
+            match found.get(&code.to_lowercase()) {
+                Some(id) => { result.ids.insert(code, *id); },
+                None if !result.missing.contains(&code) => result.missing.push(code),
+                None => {},
+            }
+        }
+        Ok(ApiResponse::success("Data resolved successfully", result).to_http_response())
+    }
+
+    /// Missing parents and a taken code are reported with the field rule errors.
+    pub async fn create<E: MasterEntity>((form, repository): (web::Json<E::Insert>, RepositoryData<E>)) -> Result<HttpResponse, AppError> {
+        let data = form.into_inner();
+
+        fail_on(repository.validate_insert(&data, data.validate().err().unwrap_or_default()).await?)?;
+
+        let id = repository.insert(&data).await?;
+        let result = repository.find_by_id(id).await?;
+        Ok(ApiResponse::success("Data inserted successfully", result).to_http_response())
+    }
+
+    pub async fn update<E: MasterEntity>((path, form, repository, reason_settings, reason_codes): (IdPath<E>, web::Json<E::Update>, RepositoryData<E>, ReasonData, ReasonCodes)) -> Result<HttpResponse, AppError> {
+        let data = form.into_inner();
+        let id = path.into_inner();
+
+        live_record(&repository, id).await?;
+
+        let errors = check_reason(&reason_settings, &reason_codes, ReasonAction::Update, &E::update_reason(&data), data.validate().err().unwrap_or_default()).await?;
+        fail_on(repository.validate_update(id, &data, errors).await?)?;
+
+        if repository.update(id, &data).await? == 0 {
+            return Err(not_changed::<E>(&repository, id, DELETED).await);
+        }
+        let result = repository.find_by_id(id).await?;
+        Ok(ApiResponse::success("Data updated successfully", result).to_http_response())
+    }
+
+    /// Applies a JSON merge patch to the stored record. Only fields present in the patch are
+    /// validated, `reason` and the updater are never taken from the stored record.
+    pub async fn patch<E: MasterEntity>((path, form, repository, reason_settings, reason_codes): (IdPath<E>, web::Json<Value>, RepositoryData<E>, ReasonData, ReasonCodes)) -> Result<HttpResponse, AppError> {
+        let patch = form.into_inner();
+        let id = path.into_inner();
+
+        if !patch.is_object() {
+            return Err(AppError::Validation("patch must be a JSON object".into()));
+        }
+
+        let current = live_record(&repository, id).await?;
+        let mut document = serde_json::to_value(&current).map_err(|error| AppError::Internal(error.to_string()))?;
+        if let Value::Object(fields) = &mut document {
+            for field in AUDIT_FIELDS {
+                fields.remove(field);
+            }
+        }
+        let stored: Vec<String> = document.as_object().map(|fields| fields.keys().cloned().collect()).unwrap_or_default();
+
+        merge_patch(&mut document, &patch);
+        let data: E::Update = serde_json::from_value(document).map_err(|error| AppError::Validation(error.to_string()))?;
+
+        let mut errors = data.validate().err().unwrap_or_default();
+        errors.errors_mut().retain(|field, _| patch.get(*field).is_some() || !stored.iter().any(|key| key == field));
+        let errors = check_reason(&reason_settings, &reason_codes, ReasonAction::Update, &E::update_reason(&data), errors).await?;
+        fail_on(repository.validate_update(id, &data, errors).await?)?;
+
+        if repository.update(id, &data).await? == 0 {
+            return Err(not_changed::<E>(&repository, id, DELETED).await);
+        }
+        let result = repository.find_by_id(id).await?;
+        Ok(ApiResponse::success("Data updated successfully", result).to_http_response())
+    }
+
+    /// The stored row, 404 when the id is unknown and 409 when it is soft deleted.
+    async fn live_record<E: MasterEntity>(repository: &RepositoryData<E>, id: E::Id) -> Result<E::Row, AppError> {
+        let row = match repository.find_by_id(id).await {
+            Ok(row) => row,
+            Err(sqlx::Error::RowNotFound) => return Err(AppError::NotFound(format!("{} ID not found", E::LABEL))),
+            Err(error) => return Err(error.into()),
+        };
+        if E::row_deleted(&row) {
+            return Err(AppError::Conflict(format!("{} {} {}", E::LABEL, id, DELETED)));
+        }
+        Ok(row)
+    }
+
+    fn fail_on(errors: ValidationErrors) -> Result<(), AppError> {
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.into())
+        }
+    }
+
+    pub async fn get_dependents<E: MasterEntity>((path, repository): (IdPath<E>, RepositoryData<E>)) -> Result<HttpResponse, AppError> {
+        let id = path.into_inner();
+
+        if !repository.exists_id(id).await? {
+            return Err(AppError::NotFound(format!("{} ID not found", E::LABEL)));
+        }
+
+        let result = repository.find_dependents(id).await?;
+        Ok(ApiResponse::success("Data retrieved successfully", result).to_http_response())
+    }
+
+    /// Soft deletes the row, the body must carry `deleted_by` and a `reason` or `delete` reason
+    /// code. Refuses with 409 and the list of live dependents unless `?cascade=true` is set.
+    pub async fn delete<E: MasterEntity>((path, query, form, repository, reason_settings, reason_codes): (IdPath<E>, web::Query<DeleteQuery>, web::Json<DeleteReasonSchema>, RepositoryData<E>, ReasonData, ReasonCodes)) -> Result<HttpResponse, AppError> {
+        let form = form.into_inner();
+        let errors = form.validate().err().unwrap_or_default();
+        let reason = ReasonRequestSchema::from(form);
+        let id = path.into_inner();
+        let cascade = query.cascade.unwrap_or(false);
+
+        fail_on(check_reason(&reason_settings, &reason_codes, ReasonAction::Delete, &reason, errors).await?)?;
+
+        match repository.soft_delete(id, &reason, cascade).await? {
+            DeleteOutcome::Deleted => {},
+            DeleteOutcome::NotChanged => return Err(not_changed::<E>(&repository, id, "is already deleted").await),
+            DeleteOutcome::HasDependents(dependents) => {
+                let message = format!("{} {} still has {} dependent record(s)", E::LABEL, id, dependents.len());
+                return Ok(ApiResponse{ status: 409, message, data: Some(dependents), pagination: None }.to_http_response());
+            },
+        }
+
+        let result = repository.find_by_id(id).await?;
+        Ok(ApiResponse::success("Data deleted successfully", result).to_http_response())
+    }
+
+    /// Clears the soft delete, the body must carry `updated_by` and a `reason` or `update` reason code.
+    /// Refuses with 409 while a parent of the record is deleted.
+    pub async fn restore<E: MasterEntity>((path, form, repository, reason_settings, reason_codes): (IdPath<E>, web::Json<RestoreReasonSchema>, RepositoryData<E>, ReasonData, ReasonCodes)) -> Result<HttpResponse, AppError> {
+        let form = form.into_inner();
+        let errors = form.validate().err().unwrap_or_default();
+        let reason = ReasonRequestSchema::from(form);
+        let id = path.into_inner();
+
+        fail_on(check_reason(&reason_settings, &reason_codes, ReasonAction::Update, &reason, errors).await?)?;
+
+        match repository.restore(id, &reason).await? {
+            RestoreOutcome::Restored => {},
+            RestoreOutcome::NotChanged => return Err(not_changed::<E>(&repository, id, "is not deleted").await),
+            RestoreOutcome::ParentDeleted(parent) => {
+                return Err(AppError::Conflict(format!("{} {} cannot be restored, {} {} is deleted", E::LABEL, id, parent.label, parent.id)));
+            },
+        }
+
+        let result = repository.find_by_id(id).await?;
+        Ok(ApiResponse::success("Data restored successfully", result).to_http_response())
+    }
+
+    /// Error for a delete or restore that touched no row: 404 when the id is unknown, 409 otherwise.
+    async fn not_changed<E: MasterEntity>(repository: &RepositoryData<E>, id: E::Id, state: &str) -> AppError {
+        match repository.exists_id(id).await {
+            Ok(true) => AppError::Conflict(format!("{} {} {}", E::LABEL, id, state)),
+            Ok(false) => AppError::NotFound(format!("{} ID not found", E::LABEL)),
+            Err(error) => error.into(),
+        }
+    }
+
+    /// Expects `?<entity>_code=`, e.g. `?country_code=IDN`.
+    pub async fn exists_code<E: MasterEntity>((query, repository): (QueryParams, RepositoryData<E>)) -> Result<HttpResponse, AppError> {
+        exists_unique::<E>(E::CODE_COLUMN, &query, &repository).await
+    }
+
+    /// Expects `?<entity>_name=` and the scope of its rule, e.g. `?state_name=Java&country_id=1`.
+    pub async fn exists_name<E: MasterEntity>((query, repository): (QueryParams, RepositoryData<E>)) -> Result<HttpResponse, AppError> {
+        exists_unique::<E>(E::NAME_COLUMN, &query, &repository).await
+    }
+
+    /// Checks `field` with its `UNIQUE` rule, so scoped rules need their scope columns
+    /// in the query. `exclude_id` skips the record being edited.
+    async fn exists_unique<E: MasterEntity>(field: &'static str, query: &HashMap<String, String>, repository: &RepositoryData<E>) -> Result<HttpResponse, AppError> {
+        let rule = E::UNIQUE.iter()
+            .find(|rule| rule.field == field)
+            .ok_or_else(|| AppError::Internal(format!("{} has no unique rule for {}", E::LABEL, field)))?;
+        let check = UniqueCheck::from_query(rule, query).map_err(AppError::Validation)?;
+        let exclude = query.get("exclude_id")
+            .map(|id| id.parse::<E::Id>().map_err(|_| AppError::Validation("exclude_id must be a positive number".into())))
+            .transpose()?;
+
+        exists_response(repository.is_taken(&check, exclude).await?)
+    }
+
+    fn exists_response(exists: bool) -> Result<HttpResponse, AppError> {
+        if exists {
+            Ok(ApiResponse::success("Data exists", exists).to_http_response())
+        } else {
+            Ok(ApiResponse::success("Data not exists", exists).to_http_response())
+        }
+    }
+}
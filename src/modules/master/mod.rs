@@ -1,3 +1,4 @@
-pub mod city;
-pub mod country;
-pub mod state;
\ No newline at end of file
+pub mod export;
+pub mod import;
+pub mod master;
+pub mod tree;
\ No newline at end of file
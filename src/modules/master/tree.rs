@@ -0,0 +1,48 @@
+pub mod tree{
+    use std::collections::HashMap;
+
+    use actix_web::HttpResponse;
+
+    use crate::errors::AppError;
+    use crate::repositories::master::master_repository::master_repository::RepositoryData;
+    use crate::schemas::api_schemas::ApiResponse;
+    use crate::schemas::master::city_schema::City;
+    use crate::schemas::master::country_schema::Country;
+    use crate::schemas::master::master_id::{CountryId, StateId};
+    use crate::schemas::master::state_schema::State;
+    use crate::schemas::master::tree_schema::{CityNode, CountryNode, StateNode};
+
+    /// Full country/state/city hierarchy of live rows, for cascading dropdowns.
+    pub async fn get_tree((countries, states, cities): (RepositoryData<Country>, RepositoryData<State>, RepositoryData<City>)) -> Result<HttpResponse, AppError> {
+        let mut cities_by_state: HashMap<StateId, Vec<CityNode>> = HashMap::new();
+        for city in cities.find_all().await? {
+            cities_by_state.entry(city.state_id).or_default().push(CityNode{
+                city_id: city.city_id,
+                city_code: city.city_code,
+                city_name: city.city_name
+            });
+        }
+
+        let mut states_by_country: HashMap<CountryId, Vec<StateNode>> = HashMap::new();
+        for state in states.find_all().await? {
+            states_by_country.entry(state.country_id).or_default().push(StateNode{
+                state_id: state.state_id,
+                state_code: state.state_code,
+                state_name: state.state_name,
+                cities: cities_by_state.remove(&state.state_id).unwrap_or_default()
+            });
+        }
+
+        let result: Vec<CountryNode> = countries.find_all().await?
+            .into_iter()
+            .map(|country| CountryNode{
+                country_id: country.country_id,
+                country_code: country.country_code,
+                country_name: country.country_name,
+                states: states_by_country.remove(&country.country_id).unwrap_or_default()
+            })
+            .collect();
+
+        Ok(ApiResponse::success("Data retrieved successfully", result).to_http_response())
+    }
+}
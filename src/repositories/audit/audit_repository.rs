@@ -0,0 +1,118 @@
+pub mod audit_repository{
+    use std::sync::{Arc, RwLock};
+
+    use actix_web::web;
+    use async_trait::async_trait;
+    use sqlx::{MySql, MySqlPool, QueryBuilder};
+
+    use crate::schemas::audit::audit_schema::{AuditEntrySchema, AuditFilter};
+
+    const SELECT: &str = "select audit_id, table_name as entity, record_id, action, changed_by, reason, reason_code, changed_on, old_values, new_values, changes
+        from precise.audit_trail";
+
+    #[async_trait]
+    pub trait AuditRepository: Send + Sync {
+        /// One page of matching entries, newest first, and the number of entries matching the filter.
+        async fn find_page(&self, filter: &AuditFilter) -> Result<(Vec<AuditEntrySchema>, i64), sqlx::Error>;
+    }
+
+    pub type AuditRepositoryData = web::Data<dyn AuditRepository>;
+
+    /// Audit repository backed by MySQL.
+    pub fn audit_repository(pool: MySqlPool) -> AuditRepositoryData {
+        web::Data::from(Arc::new(MySqlAuditRepository::new(pool)) as Arc<dyn AuditRepository>)
+    }
+
+    /// Entity name `write_audit` stores for `table`, i.e. without the schema prefix.
+    pub fn audit_entity(table: &'static str) -> &'static str {
+        table.rsplit('.').next().unwrap_or(table)
+    }
+
+    pub struct MySqlAuditRepository{
+        pool: MySqlPool
+    }
+
+    impl MySqlAuditRepository{
+        pub fn new(pool: MySqlPool) -> Self{
+            Self { pool }
+        }
+    }
+
+    fn push_conditions<'a>(builder: &mut QueryBuilder<'a, MySql>, filter: &'a AuditFilter) {
+        builder.push(" where 1 = 1");
+
+        if let Some(user) = &filter.user {
+            builder.push(" and changed_by = ").push_bind(user);
+        }
+        if let Some(entity) = &filter.entity {
+            builder.push(" and table_name = ").push_bind(entity);
+        }
+        if let Some(record_id) = filter.record_id {
+            builder.push(" and record_id = ").push_bind(record_id);
+        }
+        if let Some(from) = filter.from {
+            builder.push(" and changed_on >= ").push_bind(from);
+        }
+        if let Some(to) = filter.to.and_then(|to| to.succ_opt()) {
+            builder.push(" and changed_on < ").push_bind(to);
+        }
+    }
+
+    #[async_trait]
+    impl AuditRepository for MySqlAuditRepository {
+        async fn find_page(&self, filter: &AuditFilter) -> Result<(Vec<AuditEntrySchema>, i64), sqlx::Error> {
+            let mut count = QueryBuilder::<MySql>::new("select count(*) from precise.audit_trail");
+            push_conditions(&mut count, filter);
+            let total = count.build_query_scalar::<i64>().fetch_one(&self.pool).await?;
+
+            let mut page = QueryBuilder::<MySql>::new(SELECT);
+            push_conditions(&mut page, filter);
+            page.push(" order by changed_on desc, audit_id desc limit ")
+                .push_bind(filter.per_page)
+                .push(" offset ")
+                .push_bind(filter.offset());
+            let entries = page.build_query_as::<AuditEntrySchema>().fetch_all(&self.pool).await?;
+
+            Ok((entries, total))
+        }
+    }
+
+    /// Keeps entries in memory, for exercising handlers without a database.
+    pub struct InMemoryAuditRepository{
+        entries: RwLock<Vec<AuditEntrySchema>>
+    }
+
+    impl InMemoryAuditRepository{
+        pub fn new(entries: Vec<AuditEntrySchema>) -> Self{
+            Self { entries: RwLock::new(entries) }
+        }
+    }
+
+    impl Default for InMemoryAuditRepository{
+        fn default() -> Self{
+            Self::new(Vec::new())
+        }
+    }
+
+    #[async_trait]
+    impl AuditRepository for InMemoryAuditRepository {
+        async fn find_page(&self, filter: &AuditFilter) -> Result<(Vec<AuditEntrySchema>, i64), sqlx::Error> {
+            let mut matches: Vec<AuditEntrySchema> = self.entries.read().unwrap()
+                .iter()
+                .filter(|entry| filter.user.as_ref().is_none_or(|user| entry.changed_by.as_ref() == Some(user)))
+                .filter(|entry| filter.entity.as_ref().is_none_or(|entity| entry.entity == *entity))
+                .filter(|entry| filter.record_id.is_none_or(|record_id| entry.record_id == record_id))
+                .filter(|entry| filter.in_range(entry.changed_on))
+                .cloned()
+                .collect();
+            let total = matches.len() as i64;
+
+            matches.sort_by(|a, b| b.changed_on.cmp(&a.changed_on).then(b.audit_id.cmp(&a.audit_id)));
+            let page = matches.into_iter()
+                .skip(filter.offset() as usize)
+                .take(filter.per_page as usize)
+                .collect();
+            Ok((page, total))
+        }
+    }
+}
@@ -0,0 +1 @@
+pub mod audit_repository;
\ No newline at end of file
@@ -0,0 +1,833 @@
+pub mod master_repository{
+    use std::cmp::Ordering;
+    use std::marker::PhantomData;
+    use std::sync::{Arc, RwLock};
+
+    use actix_web::web;
+    use async_trait::async_trait;
+    use futures::channel::mpsc;
+    use futures::stream::{self, BoxStream};
+    use futures::{SinkExt, StreamExt};
+    use serde_json::Value;
+    use sqlx::mysql::MySqlArguments;
+    use sqlx::query::Query;
+    use sqlx::{MySql, MySqlConnection, MySqlPool};
+    use validator::{ValidationError, ValidationErrors};
+
+    use crate::modules::helper::audit::audit::{snapshot, write_audit, AuditAction, AuditRecord, AuditTarget};
+    use crate::repositories::master::validation_context::validation_context::{unique_message, unique_taken, ValidationContext};
+    use crate::schemas::helper::reason_schema::ReasonRequestSchema;
+    use crate::schemas::master::dependent_schema::{DeleteOutcome, Dependent, DependentSchema, RestoreOutcome};
+    use crate::schemas::master::import_schema::ImportChange;
+    use crate::schemas::master::list_schema::ListQuery;
+    use crate::schemas::master::master_entity::{ColumnValue, ImportParent, MasterEntity, Reference};
+    use crate::schemas::master::unique_schema::{normalize, UniqueCheck};
+
+    #[async_trait]
+    pub trait MasterRepository<E: MasterEntity>: Send + Sync {
+        /// Every row that is not soft deleted, ordered by id.
+        async fn find_all(&self) -> Result<Vec<E::Row>, sqlx::Error>;
+
+        /// Returns one page of rows and the number of rows matching the filters.
+        async fn find_page(&self, query: &ListQuery) -> Result<(Vec<E::Row>, i64), sqlx::Error>;
+
+        /// Every row matching the filters, search and sort of `query`, paging ignored,
+        /// yielded as the database returns them.
+        fn export(&self, query: &ListQuery) -> BoxStream<'static, Result<E::Row, sqlx::Error>>;
+
+        /// Fails with `sqlx::Error::RowNotFound` when the id does not exist.
+        async fn find_by_id(&self, id: E::Id) -> Result<E::Row, sqlx::Error>;
+
+        /// Exact match on the code column, fails with `sqlx::Error::RowNotFound` when no row has it.
+        async fn find_by_code(&self, code: &str) -> Result<E::Row, sqlx::Error>;
+
+        /// `(code, id)` of the rows holding any of `codes`, deleted rows included.
+        async fn resolve_codes(&self, codes: &[String]) -> Result<Vec<(String, E::Id)>, sqlx::Error>;
+
+        /// Returns the generated id. Writes, like every change below, its audit row in the same transaction.
+        async fn insert(&self, data: &E::Insert) -> Result<E::Id, sqlx::Error>;
+
+        /// Audits the change with the reason carried by `data`, returns the number of affected rows.
+        /// Deleted rows are not updated.
+        async fn update(&self, id: E::Id, data: &E::Update) -> Result<u64, sqlx::Error>;
+
+        /// `(code, id)` of the live `parent` rows holding any of `codes`.
+        async fn resolve_parent(&self, parent: &ImportParent, codes: &[String]) -> Result<Vec<(String, u32)>, sqlx::Error>;
+
+        /// Writes the valid rows of an import in one transaction, inserts audited with `reason`.
+        /// Returns the id of each change in order.
+        async fn import(&self, changes: &[ImportChange<E>], reason: &ReasonRequestSchema) -> Result<Vec<E::Id>, sqlx::Error>;
+
+        /// Live rows that reference the record, directly or through their own dependents.
+        async fn find_dependents(&self, id: E::Id) -> Result<Vec<DependentSchema>, sqlx::Error>;
+
+        /// Stamps `deleted_by`/`deleted_on` on a row that is not deleted yet. A row with live
+        /// dependents is refused unless `cascade` is set, then they are stamped in the same
+        /// transaction. Locks the row first, so no dependent can be added meanwhile.
+        async fn soft_delete(&self, id: E::Id, reason: &ReasonRequestSchema, cascade: bool) -> Result<DeleteOutcome, sqlx::Error>;
+
+        /// Clears the delete stamp of a deleted row. A row whose parent is deleted is refused,
+        /// the parents are locked first, so none can be deleted meanwhile.
+        async fn restore(&self, id: E::Id, reason: &ReasonRequestSchema) -> Result<RestoreOutcome, sqlx::Error>;
+
+        /// Adds the database checks of a new record, parents exist and the `UNIQUE` rules
+        /// hold, to the field rule `errors` and returns them all.
+        async fn validate_insert(&self, data: &E::Insert, errors: ValidationErrors) -> Result<ValidationErrors, sqlx::Error>;
+
+        /// Same as `validate_insert`, the record itself does not count as a duplicate.
+        async fn validate_update(&self, id: E::Id, data: &E::Update, errors: ValidationErrors) -> Result<ValidationErrors, sqlx::Error>;
+
+        async fn exists_id(&self, id: E::Id) -> Result<bool, sqlx::Error>;
+
+        /// Whether a row other than `exclude` already holds the value of `check` within its scope.
+        async fn is_taken(&self, check: &UniqueCheck, exclude: Option<E::Id>) -> Result<bool, sqlx::Error>;
+    }
+
+    /// Rows an export may read ahead of a slow client.
+    const EXPORT_BUFFER: usize = 64;
+
+    /// How handlers extract the repository of `E` from the app data.
+    pub type RepositoryData<E> = web::Data<dyn MasterRepository<E>>;
+
+    /// Repository for `E` backed by MySQL.
+    pub fn master_repository<E: MasterEntity>(pool: MySqlPool) -> RepositoryData<E> {
+        web::Data::from(Arc::new(MySqlMasterRepository::<E>::new(pool)) as Arc<dyn MasterRepository<E>>)
+    }
+
+    pub struct MySqlMasterRepository<E>{
+        pool: MySqlPool,
+        entity: PhantomData<fn() -> E>
+    }
+
+    impl<E: MasterEntity> MySqlMasterRepository<E>{
+        pub fn new(pool: MySqlPool) -> Self{
+            Self { pool, entity: PhantomData }
+        }
+    }
+
+    /// Where clause and bind values for the filters and search of `query`, the cursor excluded.
+    fn list_conditions<E: MasterEntity>(query: &ListQuery) -> (Vec<String>, Vec<String>) {
+        let mut conditions = Vec::new();
+        let mut values = Vec::new();
+
+        if !query.include_deleted {
+            conditions.push("m.deleted_on is null".to_string());
+        }
+
+        for filter in &query.filters {
+            conditions.push(format!("{} = ?", filter.column));
+            values.push(filter.value.clone());
+        }
+
+        if let Some(q) = &query.q {
+            let escaped = q.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+            conditions.push(format!("(m.{} like ? or m.{} like ?)", E::CODE_COLUMN, E::NAME_COLUMN));
+            values.push(format!("%{}%", escaped));
+            values.push(format!("%{}%", escaped));
+        }
+
+        (conditions, values)
+    }
+
+    fn where_clause(conditions: &[String]) -> String {
+        if conditions.is_empty() {
+            String::new()
+        } else {
+            format!(" where {}", conditions.join(" and "))
+        }
+    }
+
+    /// Requested sort columns, ties broken by id.
+    fn order_clause<E: MasterEntity>(query: &ListQuery) -> String {
+        let mut order: Vec<String> = query.sort.iter()
+            .map(|sort| format!("{} {}", sort.column, if sort.descending { "desc" } else { "asc" }))
+            .collect();
+        order.push(format!("m.{}", E::ID_COLUMN));
+        order.join(", ")
+    }
+
+    /// Walks `dependents` below `id` level by level, keeping rows that are not deleted.
+    /// With `lock` the rows found are locked for update, which also holds off new rows below them.
+    async fn collect_dependents(connection: &mut MySqlConnection, dependents: &'static [Dependent], id: u32, lock: bool) -> Result<Vec<(&'static Dependent, DependentSchema)>, sqlx::Error> {
+        let mut found = Vec::new();
+        let mut pending: Vec<(&'static Dependent, u32)> = dependents.iter().map(|dependent| (dependent, id)).collect();
+
+        while let Some((dependent, parent_id)) = pending.pop() {
+            let sql = format!(
+                "select {}, {}, {} from {} where {} = ? and deleted_on is null order by {}{}",
+                dependent.id_column, dependent.code_column, dependent.name_column,
+                dependent.table, dependent.parent_column, dependent.id_column,
+                if lock { " for update" } else { "" }
+            );
+            let rows = sqlx::query_as::<_, (u32, String, String)>(&sql)
+                .bind(parent_id)
+                .fetch_all(&mut *connection)
+                .await?;
+
+            for (child_id, code, name) in rows {
+                pending.extend(dependent.dependents.iter().map(|child| (child, child_id)));
+                found.push((dependent, DependentSchema{ entity: dependent.label.to_string(), id: child_id, code, name }));
+            }
+        }
+        Ok(found)
+    }
+
+    pub fn unique_checks<E: MasterEntity>(record: &Value) -> Vec<UniqueCheck> {
+        E::UNIQUE.iter().filter_map(|rule| UniqueCheck::from_record(rule, record)).collect()
+    }
+
+    /// The stored row with the fields of `data` laid over it, so scope columns the
+    /// update does not carry keep their stored value.
+    pub fn updated_record<E: MasterEntity>(row: &E::Row, data: &E::Update) -> Value {
+        let mut record = serde_json::to_value(row).unwrap_or(Value::Null);
+
+        if let (Value::Object(fields), Ok(Value::Object(changes))) = (&mut record, serde_json::to_value(data)) {
+            fields.extend(changes);
+        }
+        record
+    }
+
+    /// Adds the key column of every `UNIQUE` rule whose field is written.
+    fn with_keys<E: MasterEntity>(mut values: Vec<(&'static str, ColumnValue)>) -> Vec<(&'static str, ColumnValue)> {
+        let keys: Vec<(&'static str, ColumnValue)> = E::UNIQUE.iter()
+            .filter_map(|rule| values.iter().find_map(|(column, value)| match value {
+                ColumnValue::Text(Some(text)) if *column == rule.field => Some((rule.key, ColumnValue::Text(Some(normalize(text))))),
+                _ => None,
+            }))
+            .collect();
+        values.extend(keys);
+        values
+    }
+
+    fn bind_values<'q>(mut query: Query<'q, MySql, MySqlArguments>, values: Vec<(&'static str, ColumnValue)>) -> Query<'q, MySql, MySqlArguments> {
+        for (_, value) in values {
+            query = match value {
+                ColumnValue::Text(text) => query.bind(text),
+                ColumnValue::Id(id) => query.bind(id),
+                ColumnValue::Flag(flag) => query.bind(flag),
+            };
+        }
+        query
+    }
+
+    /// Inserts and audits one row on the caller's transaction, `reason` is recorded when given.
+    async fn insert_row<E: MasterEntity>(connection: &mut MySqlConnection, data: &E::Insert, reason: Option<&ReasonRequestSchema>) -> Result<E::Id, sqlx::Error> {
+        let values = with_keys::<E>(E::insert_values(data));
+        let columns: Vec<&str> = values.iter().map(|(column, _)| *column).collect();
+        let sql = format!(
+            "insert into {}({}) values({})",
+            E::TABLE,
+            columns.join(", "),
+            vec!["?"; columns.len()].join(", ")
+        );
+
+        let id = bind_values(sqlx::query(&sql), values)
+            .execute(&mut *connection)
+            .await?
+            .last_insert_id();
+        let id = u32::try_from(id).map_err(|error| sqlx::Error::Decode(Box::new(error)))?;
+
+        let target = AuditTarget::of::<E>();
+        let record = AuditRecord{
+            target: &target, id, action: AuditAction::Insert, actor: E::insert_actor(data),
+            reason: reason.and_then(ReasonRequestSchema::text), reason_code: reason.and_then(ReasonRequestSchema::code)
+        };
+        write_audit(connection, &record, None).await?;
+        Ok(E::Id::from(id))
+    }
+
+    /// Updates and audits one live row on the caller's transaction with the reason carried by
+    /// `data`. A deleted row is left alone and counts as not affected.
+    async fn update_row<E: MasterEntity>(connection: &mut MySqlConnection, id: E::Id, data: &E::Update) -> Result<u64, sqlx::Error> {
+        let values = with_keys::<E>(E::update_values(data));
+        let assignments: Vec<String> = values.iter().map(|(column, _)| format!("{} = ?", column)).collect();
+        let sql = format!("update {} set {} where {} = ? and deleted_on is null", E::TABLE, assignments.join(", "), E::ID_COLUMN);
+
+        let reason = E::update_reason(data);
+        let target = AuditTarget::of::<E>();
+
+        let old = snapshot(&mut *connection, &target, id.into()).await?;
+        let result = bind_values(sqlx::query(&sql), values)
+            .bind(id)
+            .execute(&mut *connection)
+            .await?;
+
+        if old.is_some() && result.rows_affected() > 0 {
+            let record = AuditRecord{ target: &target, id: id.into(), action: AuditAction::Update, actor: reason.updated_by.as_deref(), reason: reason.text(), reason_code: reason.code() };
+            write_audit(connection, &record, old).await?;
+        }
+        Ok(result.rows_affected())
+    }
+
+    #[async_trait]
+    impl<E: MasterEntity> MasterRepository<E> for MySqlMasterRepository<E> {
+        async fn find_all(&self) -> Result<Vec<E::Row>, sqlx::Error> {
+            let sql = format!("{} where m.deleted_on is null order by m.{}", E::SELECT, E::ID_COLUMN);
+
+            sqlx::query_as::<_, E::Row>(&sql)
+                .fetch_all(&self.pool)
+                .await
+        }
+
+        async fn find_page(&self, query: &ListQuery) -> Result<(Vec<E::Row>, i64), sqlx::Error> {
+            let (mut conditions, values) = list_conditions::<E>(query);
+
+            let count_sql = format!("select count(*) from ({}{}) t", E::SELECT, where_clause(&conditions));
+            let mut count_query = sqlx::query_scalar::<_, i64>(&count_sql);
+            for value in &values {
+                count_query = count_query.bind(value);
+            }
+            let total = count_query.fetch_one(&self.pool).await?;
+
+            if query.cursor.is_some() {
+                conditions.push(format!("m.{} > ?", E::ID_COLUMN));
+            }
+            let sql = format!(
+                "{}{} order by {} limit ? offset ?",
+                E::SELECT,
+                where_clause(&conditions),
+                order_clause::<E>(query)
+            );
+            let mut page_query = sqlx::query_as::<_, E::Row>(&sql);
+            for value in &values {
+                page_query = page_query.bind(value);
+            }
+            if let Some(cursor) = query.cursor {
+                page_query = page_query.bind(cursor).bind(query.per_page).bind(0);
+            } else {
+                page_query = page_query.bind(query.per_page).bind(query.offset());
+            }
+
+            let rows = page_query.fetch_all(&self.pool).await?;
+            Ok((rows, total))
+        }
+
+        /// Reads on a spawned task that hands rows over a small channel, so the cursor only
+        /// advances as fast as the consumer takes them.
+        fn export(&self, query: &ListQuery) -> BoxStream<'static, Result<E::Row, sqlx::Error>> {
+            let (conditions, values) = list_conditions::<E>(query);
+            let sql = format!("{}{} order by {}", E::SELECT, where_clause(&conditions), order_clause::<E>(query));
+            let pool = self.pool.clone();
+            let (mut sender, receiver) = mpsc::channel(EXPORT_BUFFER);
+
+            actix_web::rt::spawn(async move {
+                let mut export_query = sqlx::query_as::<_, E::Row>(&sql);
+                for value in &values {
+                    export_query = export_query.bind(value);
+                }
+
+                let mut rows = export_query.fetch(&pool);
+                while let Some(row) = rows.next().await {
+                    let failed = row.is_err();
+                    if sender.send(row).await.is_err() || failed {
+                        break;
+                    }
+                }
+            });
+            receiver.boxed()
+        }
+
+        async fn find_by_id(&self, id: E::Id) -> Result<E::Row, sqlx::Error> {
+            let sql = format!("{} where m.{} = ?", E::SELECT, E::ID_COLUMN);
+
+            sqlx::query_as::<_, E::Row>(&sql)
+                .bind(id)
+                .fetch_one(&self.pool)
+                .await
+        }
+
+        async fn find_by_code(&self, code: &str) -> Result<E::Row, sqlx::Error> {
+            let sql = format!("{} where m.{} = ?", E::SELECT, E::CODE_COLUMN);
+
+            sqlx::query_as::<_, E::Row>(&sql)
+                .bind(code)
+                .fetch_one(&self.pool)
+                .await
+        }
+
+        async fn resolve_codes(&self, codes: &[String]) -> Result<Vec<(String, E::Id)>, sqlx::Error> {
+            if codes.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            let sql = format!(
+                "select {}, {} from {} where {} in ({})",
+                E::CODE_COLUMN, E::ID_COLUMN, E::TABLE, E::CODE_COLUMN,
+                vec!["?"; codes.len()].join(", ")
+            );
+            let mut query = sqlx::query_as::<_, (String, E::Id)>(&sql);
+            for code in codes {
+                query = query.bind(code);
+            }
+            query.fetch_all(&self.pool).await
+        }
+
+        async fn insert(&self, data: &E::Insert) -> Result<E::Id, sqlx::Error> {
+            let mut transaction = self.pool.begin().await?;
+            let id = insert_row::<E>(&mut transaction, data, None).await?;
+
+            transaction.commit().await?;
+            Ok(id)
+        }
+
+        async fn update(&self, id: E::Id, data: &E::Update) -> Result<u64, sqlx::Error> {
+            let mut transaction = self.pool.begin().await?;
+            let affected = update_row::<E>(&mut transaction, id, data).await?;
+
+            transaction.commit().await?;
+            Ok(affected)
+        }
+
+        async fn resolve_parent(&self, parent: &ImportParent, codes: &[String]) -> Result<Vec<(String, u32)>, sqlx::Error> {
+            if codes.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            let sql = format!(
+                "select {}, {} from {} where deleted_on is null and {} in ({})",
+                parent.code_column, parent.id_column, parent.table, parent.code_column,
+                vec!["?"; codes.len()].join(", ")
+            );
+            let mut query = sqlx::query_as::<_, (String, u32)>(&sql);
+            for code in codes {
+                query = query.bind(code);
+            }
+            query.fetch_all(&self.pool).await
+        }
+
+        async fn import(&self, changes: &[ImportChange<E>], reason: &ReasonRequestSchema) -> Result<Vec<E::Id>, sqlx::Error> {
+            let mut transaction = self.pool.begin().await?;
+            let mut ids = Vec::with_capacity(changes.len());
+
+            for change in changes {
+                match change {
+                    ImportChange::Insert(data) => ids.push(insert_row::<E>(&mut transaction, data, Some(reason)).await?),
+                    ImportChange::Update(id, data) => {
+                        if update_row::<E>(&mut transaction, *id, data).await? == 0 {
+                            return Err(sqlx::Error::RowNotFound);
+                        }
+                        ids.push(*id);
+                    },
+                }
+            }
+
+            transaction.commit().await?;
+            Ok(ids)
+        }
+
+        async fn find_dependents(&self, id: E::Id) -> Result<Vec<DependentSchema>, sqlx::Error> {
+            let mut connection = self.pool.acquire().await?;
+            let dependents = collect_dependents(&mut connection, E::DEPENDENTS, id.into(), false).await?;
+
+            Ok(dependents.into_iter().map(|(_, row)| row).collect())
+        }
+
+        async fn soft_delete(&self, id: E::Id, reason: &ReasonRequestSchema, cascade: bool) -> Result<DeleteOutcome, sqlx::Error> {
+            let lock_sql = format!("select {} from {} where {} = ? and deleted_on is null for update", E::ID_COLUMN, E::TABLE, E::ID_COLUMN);
+            let sql = format!(
+                "update {} set deleted_on = current_timestamp, deleted_by = ? where {} = ?",
+                E::TABLE,
+                E::ID_COLUMN
+            );
+
+            let mut transaction = self.pool.begin().await?;
+
+            let live = sqlx::query_scalar::<_, u32>(&lock_sql)
+                .bind(id)
+                .fetch_optional(&mut *transaction)
+                .await?;
+            if live.is_none() {
+                return Ok(DeleteOutcome::NotChanged);
+            }
+
+            let dependents = collect_dependents(&mut transaction, E::DEPENDENTS, id.into(), true).await?;
+            if !cascade && !dependents.is_empty() {
+                return Ok(DeleteOutcome::HasDependents(dependents.into_iter().map(|(_, row)| row).collect()));
+            }
+
+            for (dependent, row) in dependents {
+                let sql = format!(
+                    "update {} set deleted_on = current_timestamp, deleted_by = ? where {} = ? and deleted_on is null",
+                    dependent.table,
+                    dependent.id_column
+                );
+                let target = AuditTarget::of_dependent(dependent);
+                let old = snapshot(&mut transaction, &target, row.id).await?;
+
+                sqlx::query(&sql)
+                    .bind(&reason.deleted_by)
+                    .bind(row.id)
+                    .execute(&mut *transaction)
+                    .await?;
+
+                let record = AuditRecord{ target: &target, id: row.id, action: AuditAction::Delete, actor: reason.deleted_by.as_deref(), reason: reason.text(), reason_code: reason.code() };
+                write_audit(&mut transaction, &record, old).await?;
+            }
+
+            let target = AuditTarget::of::<E>();
+            let old = snapshot(&mut transaction, &target, id.into()).await?;
+            sqlx::query(&sql)
+                .bind(&reason.deleted_by)
+                .bind(id)
+                .execute(&mut *transaction)
+                .await?;
+
+            let record = AuditRecord{ target: &target, id: id.into(), action: AuditAction::Delete, actor: reason.deleted_by.as_deref(), reason: reason.text(), reason_code: reason.code() };
+            write_audit(&mut transaction, &record, old).await?;
+
+            transaction.commit().await?;
+            Ok(DeleteOutcome::Deleted)
+        }
+
+        async fn restore(&self, id: E::Id, reason: &ReasonRequestSchema) -> Result<RestoreOutcome, sqlx::Error> {
+            let lock_sql = format!("select {} from {} where {} = ? and deleted_on is not null for update", E::ID_COLUMN, E::TABLE, E::ID_COLUMN);
+            let row_sql = format!("{} where m.{} = ?", E::SELECT, E::ID_COLUMN);
+            let sql = format!(
+                "update {} set deleted_on = null, deleted_by = null, updated_by = ? where {} = ?",
+                E::TABLE,
+                E::ID_COLUMN
+            );
+
+            let mut transaction = self.pool.begin().await?;
+
+            let deleted = sqlx::query_scalar::<_, u32>(&lock_sql)
+                .bind(id)
+                .fetch_optional(&mut *transaction)
+                .await?;
+            if deleted.is_none() {
+                return Ok(RestoreOutcome::NotChanged);
+            }
+
+            let row = sqlx::query_as::<_, E::Row>(&row_sql)
+                .bind(id)
+                .fetch_one(&mut *transaction)
+                .await?;
+            for reference in E::row_references(&row) {
+                let parent_sql = format!("select deleted_on is null from {} where {} = ? for update", reference.table, reference.id_column);
+                let live = sqlx::query_scalar::<_, bool>(&parent_sql)
+                    .bind(reference.id)
+                    .fetch_optional(&mut *transaction)
+                    .await?;
+                if live != Some(true) {
+                    return Ok(RestoreOutcome::ParentDeleted(reference));
+                }
+            }
+
+            let target = AuditTarget::of::<E>();
+            let old = snapshot(&mut transaction, &target, id.into()).await?;
+            sqlx::query(&sql)
+                .bind(&reason.updated_by)
+                .bind(id)
+                .execute(&mut *transaction)
+                .await?;
+
+            let record = AuditRecord{ target: &target, id: id.into(), action: AuditAction::Restore, actor: reason.updated_by.as_deref(), reason: reason.text(), reason_code: reason.code() };
+            write_audit(&mut transaction, &record, old).await?;
+
+            transaction.commit().await?;
+            Ok(RestoreOutcome::Restored)
+        }
+
+        async fn validate_insert(&self, data: &E::Insert, errors: ValidationErrors) -> Result<ValidationErrors, sqlx::Error> {
+            let mut context = ValidationContext::new(&self.pool, errors);
+
+            for reference in E::insert_references(data) {
+                context.reference(&reference).await?;
+            }
+            for check in unique_checks::<E>(&serde_json::to_value(data).unwrap_or(Value::Null)) {
+                context.unique(E::TABLE, &check, None).await?;
+            }
+            Ok(context.into_errors())
+        }
+
+        async fn validate_update(&self, id: E::Id, data: &E::Update, errors: ValidationErrors) -> Result<ValidationErrors, sqlx::Error> {
+            let record = updated_record::<E>(&self.find_by_id(id).await?, data);
+            let mut context = ValidationContext::new(&self.pool, errors);
+
+            for reference in E::update_references(data) {
+                context.reference(&reference).await?;
+            }
+            for check in unique_checks::<E>(&record) {
+                context.unique(E::TABLE, &check, Some((E::ID_COLUMN, id.into()))).await?;
+            }
+            Ok(context.into_errors())
+        }
+
+        async fn exists_id(&self, id: E::Id) -> Result<bool, sqlx::Error> {
+            let sql = format!("select exists (select 1 from {} where {} = ?)", E::TABLE, E::ID_COLUMN);
+
+            sqlx::query_scalar::<_, i64>(&sql)
+                .bind(id)
+                .fetch_one(&self.pool)
+                .await
+                .map(|exists| exists == 1)
+        }
+
+        async fn is_taken(&self, check: &UniqueCheck, exclude: Option<E::Id>) -> Result<bool, sqlx::Error> {
+            unique_taken(&self.pool, E::TABLE, check, exclude.map(|id| (E::ID_COLUMN, id.into()))).await
+        }
+    }
+
+    /// Whether a parent id is live, `None` when the parent table has no such row.
+    type ParentLookup = Box<dyn Fn(u32) -> Option<bool> + Send + Sync>;
+
+    /// Keeps rows in memory, for exercising handlers without a database.
+    /// Filters and sorting work on the serialized row, so filters on joined
+    /// columns the row does not carry match nothing. Changes are not audited.
+    pub struct InMemoryMasterRepository<E: MasterEntity>{
+        rows: RwLock<Vec<E::Row>>,
+        parents: Vec<(&'static str, ParentLookup)>
+    }
+
+    impl<E: MasterEntity> InMemoryMasterRepository<E>{
+        pub fn new(rows: Vec<E::Row>) -> Self{
+            Self { rows: RwLock::new(rows), parents: Vec::new() }
+        }
+
+        /// Checks references to `P` against the rows of `parents`. References to
+        /// tables without a parent repository are taken to exist.
+        pub fn with_parent<P: MasterEntity>(mut self, parents: Arc<InMemoryMasterRepository<P>>) -> Self{
+            let lookup = move |id: u32| parents.rows.read().unwrap()
+                .iter()
+                .find(|row| P::row_id(row).into() == id)
+                .map(|row| !P::row_deleted(row));
+            self.parents.push((P::TABLE, Box::new(lookup)));
+            self
+        }
+
+        fn parent_live(&self, reference: &Reference) -> Option<bool> {
+            self.parents.iter()
+                .find(|(table, _)| *table == reference.table)
+                .map(|(_, lookup)| lookup(reference.id).unwrap_or(false))
+        }
+
+        fn check_references(&self, references: Vec<Reference>, mut errors: ValidationErrors) -> ValidationErrors {
+            for reference in references {
+                if !errors.errors().contains_key(reference.field) && self.parent_live(&reference) == Some(false) {
+                    let mut error = ValidationError::new("not_found");
+                    error.message = Some(format!("{} {} does not exist", reference.label, reference.id).into());
+                    errors.add(reference.field, error);
+                }
+            }
+            errors
+        }
+
+        fn taken(&self, check: &UniqueCheck, exclude: Option<E::Id>) -> bool {
+            self.rows.read().unwrap()
+                .iter()
+                .filter(|row| Some(E::row_id(row)) != exclude)
+                .any(|row| check.matches(&serde_json::to_value(row).unwrap_or(Value::Null)))
+        }
+
+        /// Rows passing the filters and search of `query`, in its sort order, not paged.
+        fn matching(&self, query: &ListQuery) -> Vec<E::Row> {
+            let rows = self.rows.read().unwrap();
+            let q = query.q.as_ref().map(|q| q.to_lowercase());
+
+            let mut matches: Vec<(Value, &E::Row)> = rows.iter()
+                .map(|row| (serde_json::to_value(row).unwrap_or(Value::Null), row))
+                .filter(|(_, row)| query.include_deleted || !E::row_deleted(row))
+                .filter(|(value, _)| query.filters.iter().all(|filter| match &value[filter.field] {
+                    Value::String(text) => *text == filter.value,
+                    Value::Null => false,
+                    other => filter.value.parse::<Value>().is_ok_and(|parsed| parsed == *other),
+                }))
+                .filter(|(_, row)| q.as_ref().is_none_or(|q| {
+                    E::row_code(row).to_lowercase().contains(q) || E::row_name(row).to_lowercase().contains(q)
+                }))
+                .collect();
+
+            matches.sort_by(|(a, row_a), (b, row_b)| {
+                query.sort.iter()
+                    .map(|sort| {
+                        let ordering = compare_values(&a[sort.field], &b[sort.field]);
+                        if sort.descending { ordering.reverse() } else { ordering }
+                    })
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or_else(|| E::row_id(row_a).cmp(&E::row_id(row_b)))
+            });
+            matches.into_iter().map(|(_, row)| row.clone()).collect()
+        }
+
+        fn check_unique(&self, record: &Value, exclude: Option<E::Id>, mut errors: ValidationErrors) -> ValidationErrors {
+            for check in unique_checks::<E>(record) {
+                if !errors.errors().contains_key(check.field) && self.taken(&check, exclude) {
+                    let mut error = ValidationError::new("unique");
+                    error.message = Some(unique_message(&check).into());
+                    errors.add(check.field, error);
+                }
+            }
+            errors
+        }
+    }
+
+    impl<E: MasterEntity> Default for InMemoryMasterRepository<E>{
+        fn default() -> Self{
+            Self::new(Vec::new())
+        }
+    }
+
+    #[async_trait]
+    impl<E: MasterEntity> MasterRepository<E> for InMemoryMasterRepository<E> {
+        async fn find_all(&self) -> Result<Vec<E::Row>, sqlx::Error> {
+            let mut rows: Vec<E::Row> = self.rows.read().unwrap().iter().filter(|row| !E::row_deleted(row)).cloned().collect();
+            rows.sort_by_key(E::row_id);
+            Ok(rows)
+        }
+
+        async fn find_page(&self, query: &ListQuery) -> Result<(Vec<E::Row>, i64), sqlx::Error> {
+            let matches = self.matching(query);
+            let total = matches.len() as i64;
+
+            let page = matches.into_iter()
+                .filter(|row| query.cursor.is_none_or(|cursor| E::row_id(row).into() > cursor))
+                .skip(if query.cursor.is_some() { 0 } else { query.offset() as usize })
+                .take(query.per_page as usize)
+                .collect();
+            Ok((page, total))
+        }
+
+        fn export(&self, query: &ListQuery) -> BoxStream<'static, Result<E::Row, sqlx::Error>> {
+            stream::iter(self.matching(query).into_iter().map(Ok)).boxed()
+        }
+
+        async fn find_by_id(&self, id: E::Id) -> Result<E::Row, sqlx::Error> {
+            self.rows.read().unwrap()
+                .iter()
+                .find(|row| E::row_id(row) == id)
+                .cloned()
+                .ok_or(sqlx::Error::RowNotFound)
+        }
+
+        async fn find_by_code(&self, code: &str) -> Result<E::Row, sqlx::Error> {
+            self.rows.read().unwrap()
+                .iter()
+                .find(|row| E::row_code(row) == code)
+                .cloned()
+                .ok_or(sqlx::Error::RowNotFound)
+        }
+
+        async fn resolve_codes(&self, codes: &[String]) -> Result<Vec<(String, E::Id)>, sqlx::Error> {
+            Ok(self.rows.read().unwrap()
+                .iter()
+                .filter(|row| codes.iter().any(|code| code == E::row_code(row)))
+                .map(|row| (E::row_code(row).to_string(), E::row_id(row)))
+                .collect())
+        }
+
+        async fn insert(&self, data: &E::Insert) -> Result<E::Id, sqlx::Error> {
+            let mut rows = self.rows.write().unwrap();
+            let id = E::Id::from(rows.iter().map(|row| E::row_id(row).into()).max().unwrap_or(0) + 1);
+
+            rows.push(E::new_row(id, data));
+            Ok(id)
+        }
+
+        async fn update(&self, id: E::Id, data: &E::Update) -> Result<u64, sqlx::Error> {
+            let mut rows = self.rows.write().unwrap();
+
+            match rows.iter_mut().find(|row| E::row_id(row) == id && !E::row_deleted(row)) {
+                Some(row) => {
+                    E::apply_update(row, data);
+                    Ok(1)
+                },
+                None => Ok(0),
+            }
+        }
+
+        /// In-memory rows know no other table, so every parent code is unknown.
+        async fn resolve_parent(&self, _parent: &ImportParent, _codes: &[String]) -> Result<Vec<(String, u32)>, sqlx::Error> {
+            Ok(Vec::new())
+        }
+
+        /// Applies the changes to a copy of the rows, kept only when every change succeeds.
+        async fn import(&self, changes: &[ImportChange<E>], _reason: &ReasonRequestSchema) -> Result<Vec<E::Id>, sqlx::Error> {
+            let mut rows = self.rows.write().unwrap();
+            let mut staged = rows.clone();
+            let mut next_id = staged.iter().map(|row| E::row_id(row).into()).max().unwrap_or(0) + 1;
+            let mut ids = Vec::with_capacity(changes.len());
+
+            for change in changes {
+                match change {
+                    ImportChange::Insert(data) => {
+                        let id = E::Id::from(next_id);
+                        next_id += 1;
+                        staged.push(E::new_row(id, data));
+                        ids.push(id);
+                    },
+                    ImportChange::Update(id, data) => {
+                        let row = staged.iter_mut()
+                            .find(|row| E::row_id(row) == *id && !E::row_deleted(row))
+                            .ok_or(sqlx::Error::RowNotFound)?;
+                        E::apply_update(row, data);
+                        ids.push(*id);
+                    },
+                }
+            }
+
+            *rows = staged;
+            Ok(ids)
+        }
+
+        /// Only sees its own table, so no record ever has dependents.
+        async fn find_dependents(&self, _id: E::Id) -> Result<Vec<DependentSchema>, sqlx::Error> {
+            Ok(Vec::new())
+        }
+
+        async fn soft_delete(&self, id: E::Id, reason: &ReasonRequestSchema, _cascade: bool) -> Result<DeleteOutcome, sqlx::Error> {
+            let mut rows = self.rows.write().unwrap();
+
+            match rows.iter_mut().find(|row| E::row_id(row) == id && !E::row_deleted(row)) {
+                Some(row) => {
+                    E::set_deleted(row, Some(reason.deleted_by.clone().unwrap_or_default()));
+                    Ok(DeleteOutcome::Deleted)
+                },
+                None => Ok(DeleteOutcome::NotChanged),
+            }
+        }
+
+        async fn restore(&self, id: E::Id, _reason: &ReasonRequestSchema) -> Result<RestoreOutcome, sqlx::Error> {
+            let mut rows = self.rows.write().unwrap();
+
+            let Some(row) = rows.iter_mut().find(|row| E::row_id(row) == id && E::row_deleted(row)) else {
+                return Ok(RestoreOutcome::NotChanged);
+            };
+            if let Some(reference) = E::row_references(row).into_iter().find(|reference| self.parent_live(reference) == Some(false)) {
+                return Ok(RestoreOutcome::ParentDeleted(reference));
+            }
+            E::set_deleted(row, None);
+            Ok(RestoreOutcome::Restored)
+        }
+
+        /// Parents are only checked for the tables added with `with_parent`.
+        async fn validate_insert(&self, data: &E::Insert, errors: ValidationErrors) -> Result<ValidationErrors, sqlx::Error> {
+            let errors = self.check_references(E::insert_references(data), errors);
+            Ok(self.check_unique(&serde_json::to_value(data).unwrap_or(Value::Null), None, errors))
+        }
+
+        async fn validate_update(&self, id: E::Id, data: &E::Update, errors: ValidationErrors) -> Result<ValidationErrors, sqlx::Error> {
+            let record = updated_record::<E>(&self.find_by_id(id).await?, data);
+            let errors = self.check_references(E::update_references(data), errors);
+            Ok(self.check_unique(&record, Some(id), errors))
+        }
+
+        async fn exists_id(&self, id: E::Id) -> Result<bool, sqlx::Error> {
+            Ok(self.rows.read().unwrap().iter().any(|row| E::row_id(row) == id))
+        }
+
+        async fn is_taken(&self, check: &UniqueCheck, exclude: Option<E::Id>) -> Result<bool, sqlx::Error> {
+            Ok(self.taken(check, exclude))
+        }
+    }
+
+    fn compare_values(a: &Value, b: &Value) -> Ordering {
+        match (a, b) {
+            (Value::Number(a), Value::Number(b)) => a.as_f64().partial_cmp(&b.as_f64()).unwrap_or(Ordering::Equal),
+            (Value::String(a), Value::String(b)) => a.cmp(b),
+            (Value::Null, Value::Null) => Ordering::Equal,
+            (Value::Null, _) => Ordering::Less,
+            (_, Value::Null) => Ordering::Greater,
+            _ => Ordering::Equal,
+        }
+    }
+}
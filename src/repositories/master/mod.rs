@@ -0,0 +1,2 @@
+pub mod master_repository;
+pub mod validation_context;
\ No newline at end of file
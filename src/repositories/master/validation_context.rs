@@ -0,0 +1,107 @@
+pub mod validation_context{
+    use std::borrow::Cow;
+
+    use sqlx::MySqlPool;
+    use validator::{ValidationError, ValidationErrors};
+
+    use crate::schemas::master::master_entity::Reference;
+    use crate::schemas::master::unique_schema::UniqueCheck;
+
+    /// Database checks run as part of request validation. Borrows the pool of the
+    /// repository that builds it and collects its failures next to the field rule
+    /// errors it starts from. Fields that already failed a rule are not checked again.
+    pub struct ValidationContext<'a>{
+        pool: &'a MySqlPool,
+        errors: ValidationErrors
+    }
+
+    impl<'a> ValidationContext<'a>{
+        pub fn new(pool: &'a MySqlPool, errors: ValidationErrors) -> Self{
+            Self { pool, errors }
+        }
+
+        /// The referenced parent must exist and not be soft deleted.
+        pub async fn reference(&mut self, reference: &Reference) -> Result<(), sqlx::Error> {
+            if self.failed(reference.field) {
+                return Ok(());
+            }
+
+            let sql = format!(
+                "select exists (select 1 from {} where {} = ? and deleted_on is null)",
+                reference.table, reference.id_column
+            );
+            let exists = sqlx::query_scalar::<_, i64>(&sql)
+                .bind(reference.id)
+                .fetch_one(self.pool)
+                .await?;
+
+            if exists != 1 {
+                self.add(reference.field, "not_found", format!("{} {} does not exist", reference.label, reference.id));
+            }
+            Ok(())
+        }
+
+        /// No other row of `table` may hold the checked value within its scope.
+        /// `exclude` names the id column and id of the row being updated.
+        pub async fn unique(&mut self, table: &str, check: &UniqueCheck, exclude: Option<(&str, u32)>) -> Result<(), sqlx::Error> {
+            if self.failed(check.field) {
+                return Ok(());
+            }
+
+            if unique_taken(self.pool, table, check, exclude).await? {
+                self.add(check.field, "unique", unique_message(check));
+            }
+            Ok(())
+        }
+
+        pub fn into_errors(self) -> ValidationErrors {
+            self.errors
+        }
+
+        fn failed(&self, field: &str) -> bool {
+            self.errors.errors().contains_key(field)
+        }
+
+        fn add(&mut self, field: &'static str, code: &'static str, message: String) {
+            let mut error = ValidationError::new(code);
+            error.message = Some(Cow::Owned(message));
+            self.errors.add(field, error);
+        }
+    }
+
+    /// Whether another row of `table` holds the normalized value of `check` within its scope,
+    /// soft deleted rows included. Looks up the key column, which the unique index covers.
+    pub async fn unique_taken(pool: &MySqlPool, table: &str, check: &UniqueCheck, exclude: Option<(&str, u32)>) -> Result<bool, sqlx::Error> {
+        let mut sql = format!("select exists (select 1 from {} where {} = ?", table, check.key);
+        for (column, _) in &check.scope {
+            sql.push_str(&format!(" and {} <=> ?", column));
+        }
+        if let Some((id_column, _)) = exclude {
+            sql.push_str(&format!(" and {} <> ?", id_column));
+        }
+        sql.push(')');
+
+        let mut query = sqlx::query_scalar::<_, i64>(&sql).bind(&check.value);
+        for (_, value) in &check.scope {
+            query = query.bind(value);
+        }
+        if let Some((_, id)) = exclude {
+            query = query.bind(id);
+        }
+
+        query.fetch_one(pool).await.map(|exists| exists == 1)
+    }
+
+    /// `state_name java is already used for country_id 1`
+    pub fn unique_message(check: &UniqueCheck) -> String {
+        let scope: Vec<String> = check.scope.iter()
+            .map(|(column, value)| format!("{} {}", column, value.as_deref().unwrap_or("null")))
+            .collect();
+
+        if scope.is_empty() {
+            format!("{} {} is already used", check.field, check.value)
+        } else {
+            format!("{} {} is already used for {}", check.field, check.value, scope.join(", "))
+        }
+    }
+}
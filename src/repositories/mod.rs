@@ -0,0 +1,2 @@
+pub mod audit;
+pub mod master;
\ No newline at end of file
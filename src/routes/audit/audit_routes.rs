@@ -0,0 +1,12 @@
+pub mod audit_routes{
+    use actix_web::web;
+
+    use crate::modules::audit::audit::audit;
+
+    pub fn audit_routes(cfg: &mut web::ServiceConfig) {
+        cfg.service(
+            web::resource("/audit")
+                .route(web::get().to(audit::get_audit))
+        );
+    }
+}
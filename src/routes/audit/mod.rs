@@ -0,0 +1 @@
+pub mod audit_routes;
\ No newline at end of file
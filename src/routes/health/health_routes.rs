@@ -0,0 +1,24 @@
+pub mod health_routes{
+    use actix_web::web;
+
+    use crate::modules::health::probe::probe;
+
+    /// Probes for the load balancer, mounted outside the JWT protected scope.
+    pub fn health_routes(cfg: &mut web::ServiceConfig) {
+        cfg.service(
+            web::scope("")
+            .service(
+                web::resource("/live")
+                    .route(web::get().to(probe::live))
+            )
+            .service(
+                web::resource("/ready")
+                    .route(web::get().to(probe::ready))
+            )
+            .service(
+                web::resource("/info")
+                    .route(web::get().to(probe::info))
+            )
+        );
+    }
+}
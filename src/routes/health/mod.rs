@@ -0,0 +1 @@
+pub mod health_routes;
\ No newline at end of file
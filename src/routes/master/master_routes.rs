@@ -1,17 +1,94 @@
 pub mod master_routes{
     use actix_web::web;
 
-    use crate::routes::master::{city_routes::city_routes, country_routes::country_routes, state_routes::state_routes};
+    use crate::modules::audit::audit::audit;
+    use crate::modules::master::{export::export, import::import, master::master, tree::tree};
+    use crate::schemas::master::import_schema::MAX_IMPORT_BYTES;
+    use crate::schemas::master::{city_schema::City, country_schema::Country, master_entity::MasterEntity, reason_code_schema::ReasonCode, state_schema::State};
 
     pub fn masters_routes(cfg: &mut web::ServiceConfig) {
         cfg.service(
             web::scope("/master")
             .service(
                 web::scope("")
-                .configure(city_routes::cities_routes)
-                .configure(country_routes::countries_routes)
-                .configure(state_routes::states_routes)
+                .route("/tree", web::get().to(tree::get_tree))
+                .configure(nested_resource::<Country, State>)
+                .configure(nested_resource::<Country, City>)
+                .configure(nested_resource::<State, City>)
+                .configure(master_resource::<City>)
+                .configure(master_resource::<Country>)
+                .configure(master_resource::<ReasonCode>)
+                .configure(master_resource::<State>)
             )
         );
     }
-}
\ No newline at end of file
+
+    /// Registers `/{P::PATH}/{id}/{C::PATH}` listing the children of one parent. Must be
+    /// configured before `master_resource::<P>`, whose scope would otherwise claim the path.
+    pub fn nested_resource<P: MasterEntity, C: MasterEntity>(cfg: &mut web::ServiceConfig) {
+        cfg.route(
+            &format!("/{}/{{id}}/{}", P::PATH, C::PATH),
+            web::get().to(master::get_nested::<P, C>)
+        );
+    }
+
+    /// Registers the generic handlers for `E` under `/{E::PATH}`. The matching
+    /// `web::Data<dyn MasterRepository<E>>` must be registered as app data.
+    pub fn master_resource<E: MasterEntity>(cfg: &mut web::ServiceConfig) {
+        cfg.service(
+            web::scope(&format!("/{}", E::PATH))
+            .service(
+                web::resource("")
+                    .route(web::get().to(master::get_all::<E>))
+                    .route(web::post().to(master::create::<E>))
+            )
+            .service(
+                web::resource("/by-code/{code}")
+                    .route(web::get().to(master::get_by_code::<E>))
+            )
+            .service(
+                web::resource("/resolve")
+                    .route(web::post().to(master::resolve::<E>))
+            )
+            .service(
+                web::resource("/export")
+                    .route(web::get().to(export::export::<E>))
+            )
+            .service(
+                web::resource("/import")
+                    .app_data(web::PayloadConfig::new(MAX_IMPORT_BYTES))
+                    .route(web::post().to(import::import::<E>))
+            )
+            .service(
+                web::resource("/{id}")
+                    .route(web::get().to(master::get_by_id::<E>))
+                    .route(web::put().to(master::update::<E>))
+                    .route(web::patch().to(master::patch::<E>))
+                    .route(web::delete().to(master::delete::<E>))
+            )
+            .service(
+                web::resource("/{id}/dependents")
+                    .route(web::get().to(master::get_dependents::<E>))
+            )
+            .service(
+                web::resource("/{id}/history")
+                    .route(web::get().to(audit::get_history::<E>))
+            )
+            .service(
+                web::resource("/{id}/restore")
+                    .route(web::post().to(master::restore::<E>))
+            )
+            .service(
+                web::scope("/exists")
+                .service(
+                    web::resource("/code")
+                        .route(web::get().to(master::exists_code::<E>))
+                )
+                .service(
+                    web::resource("/name")
+                        .route(web::get().to(master::exists_name::<E>))
+                )
+            )
+        );
+    }
+}
@@ -1,4 +1 @@
-pub mod city_routes;
-pub mod country_routes;
-pub mod master_routes;
-pub mod state_routes;
\ No newline at end of file
+pub mod master_routes;
\ No newline at end of file
@@ -1,3 +1,5 @@
+pub mod audit;
 pub mod auth;
+pub mod health;
 pub mod master;
 pub mod routes;
\ No newline at end of file
@@ -1,7 +1,7 @@
 pub mod routes{
     use actix_web::web;
 
-    use crate::routes::{auth::auth_routes::auth, master::master_routes::master_routes};
+    use crate::routes::{audit::audit_routes::audit_routes, auth::auth_routes::auth, master::master_routes::master_routes};
 
     pub fn all_routes(cfg: &mut web::ServiceConfig) {
         cfg.service(
@@ -10,6 +10,7 @@ pub mod routes{
                 web::scope("")
                 .configure(auth::auth_routes)
                 .configure(master_routes::masters_routes)
+                .configure(audit_routes::audit_routes)
             )
         );
     }
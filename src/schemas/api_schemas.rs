@@ -6,7 +6,20 @@ use utoipa::ToSchema;
 pub struct ApiResponse<T>{
     pub status: u16,
     pub message: String,
-    pub data: Option<T>
+    pub data: Option<T>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<Pagination>
+}
+
+/// Paging metadata returned with list responses. `page` is set for offset paging,
+/// `next_cursor` for cursor paging and stays empty on the last page.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Pagination{
+    pub total: i64,
+    pub per_page: u32,
+    pub page: Option<u32>,
+    pub next_cursor: Option<u32>
 }
 
 impl <T: Serialize> ApiResponse<T> {
@@ -14,7 +27,17 @@ impl <T: Serialize> ApiResponse<T> {
         ApiResponse{
             status: 200,
             message: message.to_string(),
-            data: Some(data)
+            data: Some(data),
+            pagination: None
+        }
+    }
+
+    pub fn paginated(message: &str, data: T, pagination: Pagination) -> Self{
+        ApiResponse{
+            status: 200,
+            message: message.to_string(),
+            data: Some(data),
+            pagination: Some(pagination)
         }
     }
 
@@ -22,7 +45,8 @@ impl <T: Serialize> ApiResponse<T> {
         ApiResponse{
             status,
             message: message.to_string(),
-            data: None
+            data: None,
+            pagination: None
         }
     }
 
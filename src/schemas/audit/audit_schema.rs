@@ -0,0 +1,123 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{prelude::FromRow, types::Json};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::schemas::master::list_schema::{check_offset, DEFAULT_PER_PAGE, MAX_PER_PAGE};
+
+/// One change recorded in `audit_trail`: who changed the record, when, why,
+/// and the audited fields before and after the change.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct AuditEntrySchema{
+    pub audit_id: u64,
+    /// Table of the changed record, e.g. `country`.
+    pub entity: String,
+    pub record_id: u32,
+    /// `insert`, `update`, `delete` or `restore`.
+    pub action: String,
+    pub changed_by: Option<String>,
+    pub reason: Option<String>,
+    pub reason_code: Option<String>,
+    pub changed_on: NaiveDateTime,
+
+    #[schema(value_type = Option<Object>)]
+    pub old_values: Option<Json<Value>>,
+
+    #[schema(value_type = Option<Object>)]
+    pub new_values: Option<Json<Value>>,
+
+    /// Fields that differ, as `{"field": {"old": .., "new": ..}}`.
+    #[schema(value_type = Option<Object>)]
+    pub changes: Option<Json<Value>>
+}
+
+/// Query parameters of `GET /audit`.
+#[derive(Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct AuditQuery{
+    /// Only changes made by this user.
+    pub user: Option<String>,
+    /// Only changes to this table, e.g. `country`.
+    pub entity: Option<String>,
+    /// First day included, e.g. `2025-01-01`.
+    pub from: Option<NaiveDate>,
+    /// Last day included.
+    pub to: Option<NaiveDate>,
+    /// Page number starting at 1.
+    pub page: Option<u32>,
+    /// Rows per page, 20 by default and at most 100.
+    pub per_page: Option<u32>
+}
+
+/// Query parameters of `GET /master/{entity}/{id}/history`.
+#[derive(Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct HistoryQuery{
+    /// Only changes made by this user.
+    pub user: Option<String>,
+    /// First day included, e.g. `2025-01-01`.
+    pub from: Option<NaiveDate>,
+    /// Last day included.
+    pub to: Option<NaiveDate>,
+    /// Page number starting at 1.
+    pub page: Option<u32>,
+    /// Rows per page, 20 by default and at most 100.
+    pub per_page: Option<u32>
+}
+
+/// Checked audit filters, newest changes first.
+pub struct AuditFilter{
+    pub user: Option<String>,
+    pub entity: Option<String>,
+    pub record_id: Option<u32>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub page: u32,
+    pub per_page: u32
+}
+
+impl AuditFilter{
+    pub fn offset(&self) -> u32 {
+        (self.page - 1) * self.per_page
+    }
+
+    /// Whether `changed_on` falls within `from` and `to`, both days included.
+    pub fn in_range(&self, changed_on: NaiveDateTime) -> bool {
+        self.from.is_none_or(|from| changed_on.date() >= from) && self.to.is_none_or(|to| changed_on.date() <= to)
+    }
+
+    fn new(user: Option<String>, entity: Option<String>, record_id: Option<u32>, from: Option<NaiveDate>, to: Option<NaiveDate>, page: Option<u32>, per_page: Option<u32>) -> Result<Self, String> {
+        let page = page.unwrap_or(1);
+        let per_page = per_page.unwrap_or(DEFAULT_PER_PAGE);
+
+        if page == 0 {
+            return Err("page must be at least 1".into());
+        }
+        if per_page == 0 || per_page > MAX_PER_PAGE {
+            return Err(format!("per_page must be between 1 and {}", MAX_PER_PAGE));
+        }
+        check_offset(page, per_page)?;
+        if let (Some(from), Some(to)) = (from, to) {
+            if from > to {
+                return Err("from must not be after to".into());
+            }
+        }
+
+        Ok(AuditFilter{ user, entity, record_id, from, to, page, per_page })
+    }
+}
+
+impl TryFrom<AuditQuery> for AuditFilter{
+    type Error = String;
+
+    fn try_from(query: AuditQuery) -> Result<Self, String> {
+        AuditFilter::new(query.user, query.entity, None, query.from, query.to, query.page, query.per_page)
+    }
+}
+
+impl HistoryQuery{
+    pub fn into_filter(self, entity: &str, record_id: u32) -> Result<AuditFilter, String> {
+        AuditFilter::new(self.user, Some(entity.to_string()), Some(record_id), self.from, self.to, self.page, self.per_page)
+    }
+}
@@ -0,0 +1 @@
+pub mod audit_schema;
\ No newline at end of file
@@ -0,0 +1,24 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LivenessSchema{
+    pub status: String
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReadinessSchema{
+    pub status: String,
+    pub database: String,
+    pub pool_size: u32,
+    pub idle_connections: usize
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BuildInfoSchema{
+    pub version: String,
+    pub git_commit: String,
+    pub profile: String,
+    pub started_on: i64,
+    pub uptime_seconds: u64
+}
@@ -0,0 +1 @@
+pub mod health_schema;
\ No newline at end of file
@@ -1 +1,2 @@
-pub mod reason_schema;
\ No newline at end of file
+pub mod reason_schema;
+pub mod seed_schema;
\ No newline at end of file
@@ -4,8 +4,84 @@ use validator::Validate;
 
 #[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
 pub struct ReasonRequestSchema{
-    #[validate(length(min = 1, message = "You should provide a reason"))]
+    /// Free text, optional once a valid `reason_code` is given.
+    #[serde(default)]
     pub reason : String,
     pub updated_by: Option<String>,
-    pub deleted_by: Option<String>
-}
\ No newline at end of file
+    pub deleted_by: Option<String>,
+
+    /// Active code of the reason code catalog for the action, required when configured.
+    pub reason_code: Option<String>
+}
+
+impl ReasonRequestSchema{
+    /// Trimmed free text, `None` when blank.
+    pub fn text(&self) -> Option<&str> {
+        Some(self.reason.trim()).filter(|text| !text.is_empty())
+    }
+
+    /// Trimmed reason code, `None` when blank.
+    pub fn code(&self) -> Option<&str> {
+        self.reason_code.as_deref().map(str::trim).filter(|code| !code.is_empty())
+    }
+}
+
+/// Body of a master delete, `deleted_by` is reported per field when missing.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct DeleteReasonSchema{
+    /// Free text, optional once a valid `reason_code` is given.
+    #[serde(default)]
+    pub reason : String,
+    #[validate(required)]
+    pub deleted_by: Option<String>,
+
+    /// Active `delete` code of the reason code catalog, required when configured.
+    pub reason_code: Option<String>
+}
+
+impl From<DeleteReasonSchema> for ReasonRequestSchema{
+    fn from(data: DeleteReasonSchema) -> Self {
+        ReasonRequestSchema{ reason: data.reason, updated_by: None, deleted_by: data.deleted_by, reason_code: data.reason_code }
+    }
+}
+
+/// Body of a master restore, `updated_by` is reported per field when missing.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct RestoreReasonSchema{
+    /// Free text, optional once a valid `reason_code` is given.
+    #[serde(default)]
+    pub reason : String,
+    #[validate(required)]
+    pub updated_by: Option<String>,
+
+    /// Active `update` code of the reason code catalog, required when configured.
+    pub reason_code: Option<String>
+}
+
+impl From<RestoreReasonSchema> for ReasonRequestSchema{
+    fn from(data: RestoreReasonSchema) -> Self {
+        ReasonRequestSchema{ reason: data.reason, updated_by: data.updated_by, deleted_by: None, reason_code: data.reason_code }
+    }
+}
+
+/// Kind of change a reason code may justify. Masters use `update` for updates and
+/// restores and `delete` for deletes, `cancel` and `scrap` are kept for transactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReasonAction{
+    Update,
+    Delete,
+    Cancel,
+    Scrap
+}
+
+impl ReasonAction{
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReasonAction::Update => "update",
+            ReasonAction::Delete => "delete",
+            ReasonAction::Cancel => "cancel",
+            ReasonAction::Scrap => "scrap",
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CountrySeed{
+    pub country_code: String,
+    pub country_name: String
+}
+
+/// Parent country is referenced by `country_code`, not by id.
+#[derive(Debug, Clone, Deserialize)]
+pub struct StateSeed{
+    pub state_code: String,
+    pub state_name: String,
+    pub country_code: String
+}
+
+/// Parent state is referenced by `state_code`, not by id.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CitySeed{
+    pub city_code: String,
+    pub city_name: String,
+    pub state_code: String
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct MasterSeed{
+    #[serde(default)]
+    pub countries: Vec<CountrySeed>,
+    #[serde(default)]
+    pub states: Vec<StateSeed>,
+    #[serde(default)]
+    pub cities: Vec<CitySeed>
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct SeedSummary{
+    pub inserted: u32,
+    pub updated: u32,
+    pub skipped: u32,
+    pub rejected: Vec<String>
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct SeedReport{
+    pub countries: SeedSummary,
+    pub states: SeedSummary,
+    pub cities: SeedSummary
+}
@@ -2,15 +2,16 @@ use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use utoipa::{IntoParams, ToSchema};
-use validator::{Validate, ValidationError};
-use crate::{utils::option::option_ts_seconds, settings::db_pool};
-use crate::schemas::api_schemas::ApiResponse;
+use validator::Validate;
+use crate::utils::option::option_ts_seconds;
+use crate::schemas::{helper::reason_schema::ReasonRequestSchema, master::{dependent_schema::Dependent, master_entity::{ColumnValue, ImportParent, MasterEntity, Reference}, master_id::{CityId, StateId}, state_schema::State, unique_schema::UniqueRule}};
 
-#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct CitiesSchema{
-    pub city_id: u8,
+    pub city_id: CityId,
     pub city_code: String,
     pub city_name: String,
+    pub state_id: StateId,
     pub state_name: String,
     pub country_name: String,
     pub created_on: Option<NaiveDateTime>,
@@ -19,14 +20,11 @@ pub struct CitiesSchema{
     #[serde(with = "option_ts_seconds")]
     pub updated_on: Option<NaiveDateTime>,
 
-    pub updated_by: Option<String>
-}
-#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
-pub struct CitySchema{
-    pub city_id: u8,
-    pub city_code: String,
-    pub city_name: String,
-    pub state_id: u8
+    pub updated_by: Option<String>,
+
+    #[serde(with = "option_ts_seconds")]
+    pub deleted_on: Option<NaiveDateTime>,
+    pub deleted_by: Option<String>
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
@@ -39,7 +37,7 @@ pub struct InsertCitySchema{
     pub city_name: String,
 
     #[validate(required)]
-    pub state_id: Option<u8>,
+    pub state_id: Option<StateId>,
 
     #[validate(required)]
     pub created_by: Option<String>
@@ -47,9 +45,20 @@ pub struct InsertCitySchema{
 
 #[derive(Debug, Serialize, Deserialize, FromRow, ToSchema, Validate)]
 pub struct UpdateCitySchema{
+    #[validate(length(min = 3, message = "city code must be at least 3 characters"))]
     pub city_code: String,
+
+    #[validate(length(min = 3, message = "city name must be at least 3 characters"))]
     pub city_name: String,
-    pub updated_by: Option<String>
+
+    #[validate(required)]
+    pub updated_by: Option<String>,
+
+    /// Free text, optional once a valid `reason_code` is given.
+    pub reason: Option<String>,
+
+    /// Active `update` code of the reason code catalog justifying this change.
+    pub reason_code: Option<String>
 }
 
 
@@ -57,107 +66,133 @@ pub struct UpdateCitySchema{
 #[into_params(parameter_in = Query)]
 pub struct CityNameQuery {
     pub city_name: String,
+    /// State the name must be unique within.
+    pub state_id: StateId,
+    /// Id of the record being edited, it does not count as a duplicate.
+    pub exclude_id: Option<u32>,
 }
 
 #[derive(Deserialize, Serialize, FromRow, ToSchema, IntoParams)]
 #[into_params(parameter_in = Query)]
 pub struct CityCodeQuery {
     pub city_code: String,
+    /// Id of the record being edited, it does not count as a duplicate.
+    pub exclude_id: Option<u32>,
 }
 
-pub async fn validate_city_code(city_code: &str) -> Result<(), ValidationError> {
-    let pool = db_pool().await;
-    let data = sqlx::query_scalar::<_, i64>("select count(*) from precise.city where city_code=?")
-        .bind(city_code)
-        .fetch_one(&pool)
-        .await
-        .map(|count| count > 1)
-        .unwrap_or(false);
-
-    if data{
-        Ok(())
-    } else {
-        let mut err = ValidationError::new("city_code_exists");
-        err.message = Some("City Code already exists".into());
-        Err(err)
+pub struct City;
+
+impl MasterEntity for City {
+    const LABEL: &'static str = "City";
+    const PATH: &'static str = "cities";
+    const TABLE: &'static str = "precise.city";
+    const ID_COLUMN: &'static str = "city_id";
+    const CODE_COLUMN: &'static str = "city_code";
+    const NAME_COLUMN: &'static str = "city_name";
+    const SELECT: &'static str = "select m.city_id, m.city_code, m.city_name, m.state_id, s.state_name, co.country_name,
+        m.created_on, m.created_by, m.updated_on, m.updated_by, m.deleted_on, m.deleted_by
+        from precise.city m
+        left join precise.state s on m.state_id = s.state_id
+        left join precise.country co on s.country_id = co.country_id";
+    const SORT_FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("city_id", "m.city_id"),
+        ("city_code", "m.city_code"),
+        ("city_name", "m.city_name"),
+        ("state_name", "s.state_name"),
+        ("country_name", "co.country_name"),
+        ("created_on", "m.created_on"),
+        ("updated_on", "m.updated_on"),
+    ];
+    const FILTER_FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("state_id", "m.state_id"),
+        ("country_id", "s.country_id"),
+    ];
+    const DEPENDENTS: &'static [Dependent] = &[];
+    const UNIQUE: &'static [UniqueRule] = &[
+        UniqueRule{ field: "city_code", key: "city_code_key", scope: &[] },
+        UniqueRule{ field: "city_name", key: "city_name_key", scope: &["state_id"] },
+    ];
+    const AUDIT_COLUMNS: &'static [&'static str] = &["city_code", "city_name", "state_id", "deleted_on", "deleted_by"];
+
+    type Id = CityId;
+    type Row = CitiesSchema;
+    type Insert = InsertCitySchema;
+    type Update = UpdateCitySchema;
+
+    fn insert_values(data: &InsertCitySchema) -> Vec<(&'static str, ColumnValue)> {
+        vec![
+            ("city_code", ColumnValue::Text(Some(data.city_code.clone()))),
+            ("city_name", ColumnValue::Text(Some(data.city_name.clone()))),
+            ("state_id", ColumnValue::Id(data.state_id.map(u32::from))),
+            ("created_by", ColumnValue::Text(data.created_by.clone())),
+        ]
+    }
+
+    fn update_values(data: &UpdateCitySchema) -> Vec<(&'static str, ColumnValue)> {
+        vec![
+            ("city_code", ColumnValue::Text(Some(data.city_code.clone()))),
+            ("city_name", ColumnValue::Text(Some(data.city_name.clone()))),
+            ("updated_by", ColumnValue::Text(data.updated_by.clone())),
+        ]
+    }
+
+    fn insert_references(data: &InsertCitySchema) -> Vec<Reference> {
+        data.state_id.map(|state_id| Reference::to::<State>("state_id", state_id)).into_iter().collect()
     }
-}
 
-#[utoipa::path(
-    get,
-    tag = "City",
-    path = "/master/cities",
-    responses(
-        (status = 200, description = "Data retrieved successfully", body = ApiResponse<CitiesSchema>),
-        (status = 500, description = "Failed to fetch data", body = ApiResponse<String>)
-    ),
-    security(("bearer_auth" = []))
-)]
-pub fn get_all_cities() {}
-
-#[utoipa::path(
-    get,
-    tag = "City",
-    path = "/master/cities/{id}",
-    responses(
-        (status = 200, description = "Data retrieved successfully", body = ApiResponse<CitiesSchema>),
-        (status = 500, description = "Failed to fetch data", body = ApiResponse<String>)
-    ),
-    security(("bearer_auth" = []))
-)]
-pub fn get_city() {}
-
-#[utoipa::path(
-    post,
-    tag = "City",
-    path = "/master/cities",
-    request_body = InsertCitySchema,
-    responses(
-        (status = 200, description = "Data retrieved successfully", body = ApiResponse<InsertCitySchema>),
-        (status = 400, description = "Invalid Input", body = ApiResponse<String>),
-        (status = 500, description = "Failed to fetch data", body = ApiResponse<String>)
-    ),
-    security(("bearer_auth" = []))
-)]
-pub fn create_city() {}
-
-#[utoipa::path(
-    put,
-    tag = "City",
-    path = "/master/cities/{id}",
-    request_body = UpdateCitySchema,
-    responses(
-        (status = 200, description = "Data retrieved successfully", body = ApiResponse<UpdateCitySchema>),
-        (status = 400, description = "Invalid input", body = ApiResponse<String>),
-        (status = 404, description = "Data not found", body = ApiResponse<String>),
-        (status = 500, description = "Failed to fetch data", body = ApiResponse<String>)
-    ),
-    security(("bearer_auth" = []))
-)]
-pub fn update_city() {}
-
-#[utoipa::path(
-    get,
-    tag = "City",
-    path = "/precise/api/master/cities/exists/code",
-    params(CityCodeQuery),
-    responses(
-        (status = 200, description="City code exists", body = ApiResponse<u8>),
-        (status = 500, description="Failed to check city code", body = ApiResponse<u8>)
-    ),
-    security(("bearer_auth" = []))
-)]
-pub fn exists_city_code() {}
-
-#[utoipa::path(
-    get,
-    tag = "Country",
-    path = "/precise/api/master/cities/exists/name",
-    params(CityNameQuery),
-    responses(
-        (status = 200, description="City name exists", body = ApiResponse<u8>),
-        (status = 500, description="Failed to check city code", body = ApiResponse<u8>)
-    ),
-    security(("bearer_auth" = []))
-)]
-pub fn exists_city_name() {}
\ No newline at end of file
+    fn row_references(row: &CitiesSchema) -> Vec<Reference> {
+        vec![Reference::to::<State>("state_id", row.state_id)]
+    }
+
+    fn import_parents() -> Vec<ImportParent> {
+        vec![ImportParent::of::<State>("state_code", "state_id")]
+    }
+
+    fn insert_actor(data: &InsertCitySchema) -> Option<&str> {
+        data.created_by.as_deref()
+    }
+
+    fn update_reason(data: &UpdateCitySchema) -> ReasonRequestSchema {
+        ReasonRequestSchema{
+            reason: data.reason.clone().unwrap_or_default(),
+            updated_by: data.updated_by.clone(),
+            deleted_by: None,
+            reason_code: data.reason_code.clone()
+        }
+    }
+
+    fn row_id(row: &CitiesSchema) -> CityId { row.city_id }
+    fn row_code(row: &CitiesSchema) -> &str { &row.city_code }
+    fn row_name(row: &CitiesSchema) -> &str { &row.city_name }
+
+    fn row_deleted(row: &CitiesSchema) -> bool { row.deleted_on.is_some() }
+
+    fn set_deleted(row: &mut CitiesSchema, deleted_by: Option<String>) {
+        row.deleted_on = deleted_by.as_ref().map(|_| chrono::Utc::now().naive_utc());
+        row.deleted_by = deleted_by;
+    }
+
+    fn new_row(id: CityId, data: &InsertCitySchema) -> CitiesSchema {
+        CitiesSchema{
+            city_id: id,
+            city_code: data.city_code.clone(),
+            city_name: data.city_name.clone(),
+            state_id: data.state_id.unwrap_or_default(),
+            state_name: String::new(),
+            country_name: String::new(),
+            created_on: Some(chrono::Utc::now().naive_utc()),
+            created_by: data.created_by.clone().unwrap_or_default(),
+            updated_on: None,
+            updated_by: None,
+            deleted_on: None,
+            deleted_by: None
+        }
+    }
+
+    fn apply_update(row: &mut CitiesSchema, data: &UpdateCitySchema) {
+        row.city_code = data.city_code.clone();
+        row.city_name = data.city_name.clone();
+        row.updated_on = Some(chrono::Utc::now().naive_utc());
+        row.updated_by = data.updated_by.clone();
+    }
+}
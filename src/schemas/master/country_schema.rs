@@ -3,12 +3,13 @@ use crate::utils::option::option_ts_seconds;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use utoipa::{IntoParams, ToSchema};
-use validator::{Validate, ValidationError};
+use validator::Validate;
 
-use crate::{schemas::api_schemas::ApiResponse, settings::db_pool};
-#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]    
+use crate::schemas::{helper::reason_schema::ReasonRequestSchema, master::{dependent_schema::Dependent, master_entity::{ColumnValue, MasterEntity}, master_id::CountryId, state_schema::State, unique_schema::UniqueRule}};
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct CountrySchema{
-    pub country_id: u8,
+    pub country_id: CountryId,
     pub country_code: String,
     pub country_name: String,
     pub created_on: Option<NaiveDateTime>,
@@ -16,7 +17,11 @@ pub struct CountrySchema{
 
     #[serde(with = "option_ts_seconds")]
     pub updated_on: Option<NaiveDateTime>,
-    pub updated_by: Option<String>
+    pub updated_by: Option<String>,
+
+    #[serde(with = "option_ts_seconds")]
+    pub deleted_on: Option<NaiveDateTime>,
+    pub deleted_by: Option<String>
 }
 
 #[derive(Debug, Validate, Serialize, Deserialize, FromRow, ToSchema)]
@@ -44,137 +49,129 @@ pub struct UpdateCountrySchema{
     #[validate(required)]
     pub updated_by: Option<String>,
 
-    #[validate(required)]
-    pub reason: Option<String>
+    /// Free text, optional once a valid `reason_code` is given.
+    pub reason: Option<String>,
+
+    /// Active `update` code of the reason code catalog justifying this change.
+    pub reason_code: Option<String>
 }
 
 #[derive(Deserialize, Serialize, FromRow, IntoParams, ToSchema)]
 #[into_params(parameter_in = Query)]
 pub struct CountryNameQuery {
     pub country_name: String,
+    /// Id of the record being edited, it does not count as a duplicate.
+    pub exclude_id: Option<u32>,
 }
 
 #[derive(Deserialize, Serialize, FromRow, IntoParams, ToSchema)]
 #[into_params(parameter_in = Query)]
 pub struct CountryCodeQuery {
     pub country_code: String,
+    /// Id of the record being edited, it does not count as a duplicate.
+    pub exclude_id: Option<u32>,
 }
 
-pub async fn validate_country_id(country_id: u8) -> Result<(), ValidationError> {
-    let pool = db_pool().await;
-    let data = sqlx::query_scalar::<_, i64>("select count(*) from precise.country where country_id=?")
-        .bind(&country_id)
-        .fetch_one(&pool)
-        .await
-        .map(|count| count > 0)
-        .unwrap_or(false);
-
-    if data{
-        Ok(())
-    } else {
-        let mut err = ValidationError::new("country_id_not_found");
-        err.message = Some("Country ID not found".into());
-        Err(err)
+pub struct Country;
+
+impl MasterEntity for Country {
+    const LABEL: &'static str = "Country";
+    const PATH: &'static str = "countries";
+    const TABLE: &'static str = "precise.country";
+    const ID_COLUMN: &'static str = "country_id";
+    const CODE_COLUMN: &'static str = "country_code";
+    const NAME_COLUMN: &'static str = "country_name";
+    const SELECT: &'static str = "select m.country_id, m.country_code, m.country_name, m.created_on, m.created_by, m.updated_on, m.updated_by, m.deleted_on, m.deleted_by
+        from precise.country m";
+    const SORT_FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("country_id", "m.country_id"),
+        ("country_code", "m.country_code"),
+        ("country_name", "m.country_name"),
+        ("created_on", "m.created_on"),
+        ("updated_on", "m.updated_on"),
+    ];
+    const FILTER_FIELDS: &'static [(&'static str, &'static str)] = &[];
+    const DEPENDENTS: &'static [Dependent] = &[
+        Dependent{
+            label: State::LABEL,
+            table: State::TABLE,
+            id_column: State::ID_COLUMN,
+            code_column: State::CODE_COLUMN,
+            name_column: State::NAME_COLUMN,
+            parent_column: "country_id",
+            audit_columns: State::AUDIT_COLUMNS,
+            dependents: State::DEPENDENTS
+        },
+    ];
+    const UNIQUE: &'static [UniqueRule] = &[
+        UniqueRule{ field: "country_code", key: "country_code_key", scope: &[] },
+        UniqueRule{ field: "country_name", key: "country_name_key", scope: &[] },
+    ];
+    const AUDIT_COLUMNS: &'static [&'static str] = &["country_code", "country_name", "deleted_on", "deleted_by"];
+
+    type Id = CountryId;
+    type Row = CountrySchema;
+    type Insert = InsertCountrySchema;
+    type Update = UpdateCountrySchema;
+
+    fn insert_values(data: &InsertCountrySchema) -> Vec<(&'static str, ColumnValue)> {
+        vec![
+            ("country_code", ColumnValue::Text(Some(data.country_code.clone()))),
+            ("country_name", ColumnValue::Text(Some(data.country_name.clone()))),
+            ("created_by", ColumnValue::Text(data.created_by.clone())),
+        ]
     }
-}
 
-pub async fn validate_country_code(country_code: String) -> Result<(), ValidationError> {
-    let pool = db_pool().await;
-    let data = sqlx::query_scalar::<_, i64>("select count(*) from precise.country where country_code=?")
-        .bind(&country_code)
-        .fetch_one(&pool)
-        .await
-        .map(|count| count == 0)
-        .unwrap_or(false);
-
-    if data{
-        Ok(())
-    } else {
-        let mut err = ValidationError::new("country_code_exists");
-        err.message = Some("Country Code already exists".into());
-        Err(err)
+    fn update_values(data: &UpdateCountrySchema) -> Vec<(&'static str, ColumnValue)> {
+        vec![
+            ("country_code", ColumnValue::Text(Some(data.country_code.clone()))),
+            ("country_name", ColumnValue::Text(Some(data.country_name.clone()))),
+            ("updated_by", ColumnValue::Text(data.updated_by.clone())),
+        ]
+    }
+
+    fn insert_actor(data: &InsertCountrySchema) -> Option<&str> {
+        data.created_by.as_deref()
     }
-}
 
-#[utoipa::path(
-    get,
-    path = "/precise/api/master/countries",
-    tag = "Country",
-    responses(
-        (status = 200, description="Countries data retrieved successfully", body = ApiResponse<CountrySchema>),
-        (status = 500, description="Failed to retrieve data", body = ApiResponse<String>)
-    ),
-    security(("bearer_auth" = []))
-)]
-pub fn get_all_countries() {}
-
-#[utoipa::path(
-    post,
-    tag = "Country",
-    path = "/precise/api/master/countries",
-    request_body= InsertCountrySchema,
-    responses(
-        (status = 200, description="Country created", body = ApiResponse<InsertCountrySchema>),
-        (status = 400, description="Invalid Input", body = ApiResponse<String>),
-        (status = 500, description="Failed to create city", body = ApiResponse<String>)
-    ),
-    security(("bearer_auth" = []))
-)]
-pub fn create_country() {}
-
-#[utoipa::path(
-    get,
-    tag = "Country",
-    path = "/precise/api/master/countries/{country_id}",
-    responses(
-        (status = 200, description="Country updated", body = ApiResponse<CountrySchema>),
-        (status = 404, description="City id does not exists", body = ApiResponse<String>),
-        (status = 500, description="Failed to retrieve data", body = ApiResponse<String>)
-    ),
-    security(("bearer_auth" = []))
-)]
-pub fn get_country() {}
-
-
-#[utoipa::path(
-    put,
-    tag = "Country",
-    path = "/precise/api/master/countries/{country_id}",
-    request_body = UpdateCountrySchema,
-    responses(
-        (status = 200, description="Country updated", body = ApiResponse<UpdateCountrySchema>),
-        (status = 400, description="Invalid Input", body = ApiResponse<String>),
-        (status = 404, description="City id does not exists", body = ApiResponse<String>),
-        (status = 500, description="Failed to update city", body = ApiResponse<String>)
-    ),
-    security(("bearer_auth" = []))
-)]
-pub fn update_country() {}
-
-#[utoipa::path(
-    get,
-    tag = "Country",
-    path = "/precise/api/master/countries/exists/code",
-    params(CountryCodeQuery),
-    responses(
-        (status = 200, description="Country code exists", body = ApiResponse<String>),
-        (status = 404, description="Country code does not exists", body = ApiResponse<String>),
-        (status = 500, description="Failed to check country code", body = ApiResponse<String>)
-    ),
-    security(("bearer_auth" = []))
-)]
-pub fn exists_country_code() {}
-
-#[utoipa::path(
-    get,
-    tag = "Country",
-    path = "/precise/api/master/countries/exists/name",
-    params(CountryNameQuery),
-    responses(
-        (status = 200, description="Country name exists", body = ApiResponse<String>),
-        (status = 404, description="Country name does not exist", body = ApiResponse<String>),
-        (status = 500, description="Failed to check country code", body = ApiResponse<String>)
-    ),
-    security(("bearer_auth" = []))
-)]
-pub fn exists_country_name() {}
\ No newline at end of file
+    fn update_reason(data: &UpdateCountrySchema) -> ReasonRequestSchema {
+        ReasonRequestSchema{
+            reason: data.reason.clone().unwrap_or_default(),
+            updated_by: data.updated_by.clone(),
+            deleted_by: None,
+            reason_code: data.reason_code.clone()
+        }
+    }
+
+    fn row_id(row: &CountrySchema) -> CountryId { row.country_id }
+    fn row_code(row: &CountrySchema) -> &str { &row.country_code }
+    fn row_name(row: &CountrySchema) -> &str { &row.country_name }
+
+    fn row_deleted(row: &CountrySchema) -> bool { row.deleted_on.is_some() }
+
+    fn set_deleted(row: &mut CountrySchema, deleted_by: Option<String>) {
+        row.deleted_on = deleted_by.as_ref().map(|_| chrono::Utc::now().naive_utc());
+        row.deleted_by = deleted_by;
+    }
+
+    fn new_row(id: CountryId, data: &InsertCountrySchema) -> CountrySchema {
+        CountrySchema{
+            country_id: id,
+            country_code: data.country_code.clone(),
+            country_name: data.country_name.clone(),
+            created_on: Some(chrono::Utc::now().naive_utc()),
+            created_by: data.created_by.clone().unwrap_or_default(),
+            updated_on: None,
+            updated_by: None,
+            deleted_on: None,
+            deleted_by: None
+        }
+    }
+
+    fn apply_update(row: &mut CountrySchema, data: &UpdateCountrySchema) {
+        row.country_code = data.country_code.clone();
+        row.country_name = data.country_name.clone();
+        row.updated_on = Some(chrono::Utc::now().naive_utc());
+        row.updated_by = data.updated_by.clone();
+    }
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::schemas::master::master_entity::Reference;
+
+/// A table whose rows point to a master entity through `parent_column`.
+/// `dependents` lists the tables pointing to this one in turn.
+pub struct Dependent {
+    pub label: &'static str,
+    pub table: &'static str,
+    pub id_column: &'static str,
+    pub code_column: &'static str,
+    pub name_column: &'static str,
+    pub parent_column: &'static str,
+    pub audit_columns: &'static [&'static str],
+    pub dependents: &'static [Dependent],
+}
+
+/// A live row that blocks deleting its parent.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DependentSchema {
+    pub entity: String,
+    pub id: u32,
+    pub code: String,
+    pub name: String,
+}
+
+/// What `MasterRepository::soft_delete` did.
+pub enum DeleteOutcome {
+    Deleted,
+    /// The id is unknown or the row is already deleted, nothing was written.
+    NotChanged,
+    /// Refused without `cascade`, nothing was written.
+    HasDependents(Vec<DependentSchema>),
+}
+
+/// What `MasterRepository::restore` did.
+pub enum RestoreOutcome {
+    Restored,
+    /// The id is unknown or the row is not deleted, nothing was written.
+    NotChanged,
+    /// A parent of the row is deleted itself, nothing was written.
+    ParentDeleted(Reference),
+}
+
+#[derive(Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct DeleteQuery {
+    /// Also soft delete every dependent row instead of refusing with 409.
+    pub cascade: Option<bool>,
+}
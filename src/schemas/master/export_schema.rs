@@ -0,0 +1,56 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// UTF-8 with a header row.
+    #[default]
+    Csv,
+    /// One JSON object per line, shaped like the list `data` items.
+    Ndjson,
+    /// A workbook with a single sheet, header in row 1.
+    Xlsx,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Ndjson => "ndjson",
+            ExportFormat::Xlsx => "xlsx",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Ndjson => "application/x-ndjson",
+            ExportFormat::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, String> {
+        match value {
+            "csv" => Ok(ExportFormat::Csv),
+            "ndjson" => Ok(ExportFormat::Ndjson),
+            "xlsx" => Ok(ExportFormat::Xlsx),
+            other => Err(format!("unknown format {}, allowed: csv, ndjson, xlsx", other)),
+        }
+    }
+}
+
+/// Parameters of the export endpoints, taken alongside the list filters, `sort`, `q` and
+/// `include_deleted`. Paging parameters are ignored, every matching row is exported.
+#[derive(Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct ExportQuery {
+    /// `csv` by default.
+    pub format: Option<ExportFormat>,
+}
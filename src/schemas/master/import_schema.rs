@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::schemas::master::master_entity::MasterEntity;
+
+/// Data rows accepted in one import file.
+pub const MAX_IMPORT_ROWS: usize = 2000;
+
+/// Largest accepted import file, in bytes.
+pub const MAX_IMPORT_BYTES: usize = 10 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    /// Only reports what each row would do.
+    #[default]
+    DryRun,
+    /// Applies the valid rows in one transaction, rejected rows are left out.
+    Commit,
+}
+
+#[derive(Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct ImportQuery {
+    /// `dry_run` by default.
+    #[serde(default)]
+    pub mode: ImportMode,
+    /// Stored as the creator or updater of the imported rows.
+    pub imported_by: String,
+    /// Free text recorded on every imported row, required with updates unless `reason_code` is given.
+    pub reason: Option<String>,
+    /// Active `update` code of the reason code catalog.
+    pub reason_code: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportStatus {
+    Insert,
+    Update,
+    Unchanged,
+    Rejected,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportRowSchema {
+    /// Row number as shown by a spreadsheet, the header is row 1.
+    pub row: u32,
+    pub code: String,
+    pub status: ImportStatus,
+    /// Stored record of an update, or the new record once an insert is committed.
+    pub id: Option<u32>,
+    /// Messages per column, the same shape as the `data` of a 400 response.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub errors: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportReportSchema {
+    pub mode: ImportMode,
+    /// Whether the valid rows were written.
+    pub committed: bool,
+    pub inserted: u32,
+    pub updated: u32,
+    pub unchanged: u32,
+    pub rejected: u32,
+    pub rows: Vec<ImportRowSchema>,
+}
+
+impl ImportReportSchema {
+    pub fn new(mode: ImportMode) -> Self {
+        ImportReportSchema{ mode, committed: false, inserted: 0, updated: 0, unchanged: 0, rejected: 0, rows: Vec::new() }
+    }
+
+    pub fn push(&mut self, row: ImportRowSchema) {
+        match row.status {
+            ImportStatus::Insert => self.inserted += 1,
+            ImportStatus::Update => self.updated += 1,
+            ImportStatus::Unchanged => self.unchanged += 1,
+            ImportStatus::Rejected => self.rejected += 1,
+        }
+        self.rows.push(row);
+    }
+}
+
+/// A valid import row, written by `MasterRepository::import`.
+pub enum ImportChange<E: MasterEntity> {
+    Insert(E::Insert),
+    Update(E::Id, E::Update),
+}
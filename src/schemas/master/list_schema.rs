@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::schemas::master::master_entity::MasterEntity;
+
+pub const DEFAULT_PER_PAGE: u32 = 20;
+pub const MAX_PER_PAGE: u32 = 100;
+
+/// Query parameters accepted by every master list endpoint. Entity specific
+/// filters such as `country_id` are passed alongside these.
+#[derive(Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct ListQueryParams {
+    /// Page number starting at 1, cannot be combined with `cursor`.
+    pub page: Option<u32>,
+    /// Rows per page, 20 by default and at most 100.
+    pub per_page: Option<u32>,
+    /// Id after which the next page starts, use 0 for the first page. Cursor pages are ordered by id.
+    pub cursor: Option<u32>,
+    /// Comma separated fields, prefix with `-` for descending, e.g. `city_name,-created_on`.
+    pub sort: Option<String>,
+    /// Case insensitive search on code and name.
+    pub q: Option<String>,
+    /// Also return soft deleted rows.
+    pub include_deleted: Option<bool>,
+}
+
+pub struct SortField {
+    pub field: &'static str,
+    pub column: &'static str,
+    pub descending: bool,
+}
+
+pub struct FilterField {
+    pub field: &'static str,
+    pub column: &'static str,
+    pub value: String,
+}
+
+/// List parameters checked against the sort and filter whitelist of an entity.
+pub struct ListQuery {
+    pub page: u32,
+    pub per_page: u32,
+    pub cursor: Option<u32>,
+    pub sort: Vec<SortField>,
+    pub filters: Vec<FilterField>,
+    pub q: Option<String>,
+    pub include_deleted: bool,
+}
+
+impl ListQuery {
+    pub fn parse<E: MasterEntity>(params: &HashMap<String, String>) -> Result<Self, String> {
+        let mut query = ListQuery {
+            page: 1,
+            per_page: DEFAULT_PER_PAGE,
+            cursor: None,
+            sort: Vec::new(),
+            filters: Vec::new(),
+            q: None,
+            include_deleted: false,
+        };
+
+        for (key, value) in params {
+            match key.as_str() {
+                "page" => {
+                    query.page = parse_number(key, value)?;
+                    if query.page == 0 {
+                        return Err("page must be at least 1".into());
+                    }
+                },
+                "per_page" => {
+                    query.per_page = parse_number(key, value)?;
+                    if query.per_page == 0 || query.per_page > MAX_PER_PAGE {
+                        return Err(format!("per_page must be between 1 and {}", MAX_PER_PAGE));
+                    }
+                },
+                "cursor" => query.cursor = Some(parse_number(key, value)?),
+                "sort" => query.sort = parse_sort::<E>(value)?,
+                "include_deleted" => {
+                    query.include_deleted = value.parse().map_err(|_| "include_deleted must be true or false".to_string())?;
+                },
+                "q" => {
+                    let q = value.trim();
+                    if !q.is_empty() {
+                        query.q = Some(q.to_string());
+                    }
+                },
+                _ => query.filter_by::<E>(key, value.clone())?,
+            }
+        }
+
+        if query.cursor.is_some() && (params.contains_key("page") || !query.sort.is_empty()) {
+            return Err("cursor cannot be combined with page or sort".into());
+        }
+        check_offset(query.page, query.per_page)?;
+
+        Ok(query)
+    }
+
+    /// Adds an exact match filter, replacing any earlier value for the same field.
+    pub fn filter_by<E: MasterEntity>(&mut self, key: &str, value: String) -> Result<(), String> {
+        let (field, column) = E::FILTER_FIELDS.iter()
+            .find(|(field, _)| *field == key)
+            .ok_or_else(|| format!("unknown filter {}, allowed: {}", key, field_names(E::FILTER_FIELDS)))?;
+
+        self.filters.retain(|filter| filter.field != *field);
+        self.filters.push(FilterField { field, column, value });
+        Ok(())
+    }
+
+    pub fn offset(&self) -> u32 {
+        (self.page - 1) * self.per_page
+    }
+}
+
+/// Rejects a `page` whose offset does not fit the `u32` bound as the SQL offset.
+/// Expects `page` and `per_page` to be at least 1.
+pub fn check_offset(page: u32, per_page: u32) -> Result<(), String> {
+    match (page - 1).checked_mul(per_page) {
+        Some(_) => Ok(()),
+        None => Err(format!("page must be at most {} with per_page {}", u32::MAX / per_page + 1, per_page)),
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("{} must be a positive number", key))
+}
+
+fn parse_sort<E: MasterEntity>(value: &str) -> Result<Vec<SortField>, String> {
+    value.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| {
+            let (name, descending) = match item.strip_prefix('-') {
+                Some(name) => (name, true),
+                None => (item, false),
+            };
+            E::SORT_FIELDS.iter()
+                .find(|(field, _)| *field == name)
+                .map(|(field, column)| SortField { field, column, descending })
+                .ok_or_else(|| format!("cannot sort by {}, allowed: {}", name, field_names(E::SORT_FIELDS)))
+        })
+        .collect()
+}
+
+fn field_names(fields: &[(&str, &str)]) -> String {
+    fields.iter().map(|(field, _)| *field).collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schemas::master::state_schema::State;
+
+    fn parse(pairs: &[(&str, &str)]) -> Result<ListQuery, String> {
+        let params = pairs.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect();
+        ListQuery::parse::<State>(&params)
+    }
+
+    #[test]
+    fn defaults_to_the_first_page() {
+        let query = parse(&[]).unwrap();
+
+        assert_eq!((query.page, query.per_page, query.offset()), (1, DEFAULT_PER_PAGE, 0));
+        assert!(query.cursor.is_none() && query.sort.is_empty() && query.filters.is_empty());
+        assert!(query.q.is_none() && !query.include_deleted);
+    }
+
+    #[test]
+    fn reads_sort_filters_and_search() {
+        let query = parse(&[("page", "3"), ("per_page", "10"), ("sort", "country_name, -state_code,"), ("country_id", "7"), ("q", "  jav "), ("include_deleted", "true")]).unwrap();
+        let sort: Vec<(&str, &str, bool)> = query.sort.iter().map(|sort| (sort.field, sort.column, sort.descending)).collect();
+
+        assert_eq!(query.offset(), 20);
+        assert_eq!(sort, vec![("country_name", "c.country_name", false), ("state_code", "m.state_code", true)]);
+        assert_eq!(query.filters.iter().map(|filter| (filter.column, filter.value.as_str())).collect::<Vec<_>>(), vec![("m.country_id", "7")]);
+        assert_eq!(query.q.as_deref(), Some("jav"));
+        assert!(query.include_deleted);
+        assert!(parse(&[("q", "   ")]).unwrap().q.is_none());
+    }
+
+    #[test]
+    fn rejects_fields_outside_the_whitelist() {
+        assert_eq!(parse(&[("sort", "password")]).err().unwrap(), "cannot sort by password, allowed: state_id, state_code, state_name, country_name, created_on, updated_on");
+        assert_eq!(parse(&[("city_id", "1")]).err().unwrap(), "unknown filter city_id, allowed: country_id");
+    }
+
+    #[test]
+    fn rejects_bad_paging() {
+        assert_eq!(parse(&[("page", "0")]).err().unwrap(), "page must be at least 1");
+        assert_eq!(parse(&[("page", "-1")]).err().unwrap(), "page must be a positive number");
+        assert_eq!(parse(&[("per_page", "101")]).err().unwrap(), "per_page must be between 1 and 100");
+        assert_eq!(parse(&[("include_deleted", "yes")]).err().unwrap(), "include_deleted must be true or false");
+        assert_eq!(parse(&[("cursor", "5"), ("page", "2")]).err().unwrap(), "cursor cannot be combined with page or sort");
+        assert_eq!(parse(&[("cursor", "5"), ("sort", "state_code")]).err().unwrap(), "cursor cannot be combined with page or sort");
+        assert!(parse(&[("cursor", "5")]).is_ok());
+    }
+
+    #[test]
+    fn rejects_pages_whose_offset_overflows() {
+        let last = (u32::MAX / 100 + 1).to_string();
+        let beyond = (u32::MAX / 100 + 2).to_string();
+
+        assert_eq!(parse(&[("page", &last), ("per_page", "100")]).unwrap().offset(), (u32::MAX / 100) * 100);
+        assert_eq!(parse(&[("page", &beyond), ("per_page", "100")]).err().unwrap(), format!("page must be at most {} with per_page 100", last));
+    }
+}
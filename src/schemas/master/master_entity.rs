@@ -0,0 +1,112 @@
+use serde::{de::DeserializeOwned, Serialize};
+use sqlx::{mysql::MySqlRow, FromRow};
+use validator::Validate;
+
+use crate::schemas::{helper::reason_schema::ReasonRequestSchema, master::{dependent_schema::Dependent, master_id::MasterId, unique_schema::UniqueRule}};
+
+/// Value written to a column by the generic insert and update statements.
+pub enum ColumnValue {
+    Text(Option<String>),
+    Id(Option<u32>),
+    Flag(Option<bool>),
+}
+
+/// Parent record a request field points to, checked to exist and not be deleted.
+pub struct Reference {
+    pub field: &'static str,
+    pub label: &'static str,
+    pub table: &'static str,
+    pub id_column: &'static str,
+    pub id: u32,
+}
+
+impl Reference {
+    pub fn to<P: MasterEntity>(field: &'static str, id: P::Id) -> Self {
+        Reference{ field, label: P::LABEL, table: P::TABLE, id_column: P::ID_COLUMN, id: id.into() }
+    }
+}
+
+/// Parent named by its code in an import file, e.g. the `state_code` column of a
+/// city sheet, resolved to the `id_field` of the insert schema.
+pub struct ImportParent {
+    pub code_field: &'static str,
+    pub id_field: &'static str,
+    pub label: &'static str,
+    pub table: &'static str,
+    pub id_column: &'static str,
+    pub code_column: &'static str,
+}
+
+impl ImportParent {
+    pub fn of<P: MasterEntity>(code_field: &'static str, id_field: &'static str) -> Self {
+        ImportParent{ code_field, id_field, label: P::LABEL, table: P::TABLE, id_column: P::ID_COLUMN, code_column: P::CODE_COLUMN }
+    }
+}
+
+/// Describes one master table. Implementing it is all a new table needs to get
+/// list, get, create, update and exists endpoints from the generic handlers,
+/// repositories and `master_resource` routes.
+pub trait MasterEntity: Send + Sync + 'static {
+    /// Singular label used in messages, e.g. `Country`.
+    const LABEL: &'static str;
+    /// Route segment below `/master`, e.g. `countries`.
+    const PATH: &'static str;
+    const TABLE: &'static str;
+    const ID_COLUMN: &'static str;
+    const CODE_COLUMN: &'static str;
+    const NAME_COLUMN: &'static str;
+    /// Query producing `Row`, the entity table must be aliased as `m`.
+    const SELECT: &'static str;
+    /// Fields accepted by `?sort=`, mapped to the column they order by.
+    const SORT_FIELDS: &'static [(&'static str, &'static str)];
+    /// Fields accepted as exact match filters, e.g. `?country_id=1`.
+    const FILTER_FIELDS: &'static [(&'static str, &'static str)];
+    /// Tables referencing this one, checked before a delete.
+    const DEPENDENTS: &'static [Dependent];
+    /// Checked on create and update and by the `exists` endpoints, which need a rule for
+    /// `CODE_COLUMN` and `NAME_COLUMN`.
+    const UNIQUE: &'static [UniqueRule];
+    /// Columns kept in the before/after values of the audit trail.
+    const AUDIT_COLUMNS: &'static [&'static str];
+
+    type Id: MasterId;
+    type Row: for<'r> FromRow<'r, MySqlRow> + Serialize + Clone + Send + Sync + Unpin + 'static;
+    type Insert: DeserializeOwned + Serialize + Validate + Send + Sync + 'static;
+    type Update: DeserializeOwned + Serialize + Validate + Send + Sync + 'static;
+
+    fn insert_values(data: &Self::Insert) -> Vec<(&'static str, ColumnValue)>;
+    fn update_values(data: &Self::Update) -> Vec<(&'static str, ColumnValue)>;
+    fn insert_actor(data: &Self::Insert) -> Option<&str>;
+    fn update_reason(data: &Self::Update) -> ReasonRequestSchema;
+
+    /// Parents named by a new record, checked during validation.
+    fn insert_references(_data: &Self::Insert) -> Vec<Reference> {
+        Vec::new()
+    }
+
+    fn update_references(_data: &Self::Update) -> Vec<Reference> {
+        Vec::new()
+    }
+
+    /// Parents a stored row points to, which must be live before it is restored.
+    fn row_references(_row: &Self::Row) -> Vec<Reference> {
+        Vec::new()
+    }
+
+    /// Parent code columns an import file may carry instead of parent ids.
+    fn import_parents() -> Vec<ImportParent> {
+        Vec::new()
+    }
+
+    fn row_id(row: &Self::Row) -> Self::Id;
+    fn row_code(row: &Self::Row) -> &str;
+    fn row_name(row: &Self::Row) -> &str;
+    fn row_deleted(row: &Self::Row) -> bool;
+
+    /// Stamps the row as deleted by `deleted_by`, or clears the stamp when `None`.
+    fn set_deleted(row: &mut Self::Row, deleted_by: Option<String>);
+
+    /// Row kept by the in-memory repository for a new record, joined columns stay empty.
+    fn new_row(id: Self::Id, data: &Self::Insert) -> Self::Row;
+    fn apply_update(row: &mut Self::Row, data: &Self::Update);
+}
@@ -0,0 +1,60 @@
+use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+use std::str::FromStr;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sqlx::{Decode, Encode, MySql, Type};
+use utoipa::ToSchema;
+
+/// Primary key of a master table. Each table has its own newtype so a state id
+/// cannot be passed where a city id is expected; all of them are `int unsigned`.
+pub trait MasterId:
+    Copy + Eq + Ord + Hash + Debug + Display + FromStr
+    + Serialize + DeserializeOwned
+    + Type<MySql> + for<'q> Encode<'q, MySql> + for<'r> Decode<'r, MySql>
+    + From<u32> + Into<u32>
+    + Send + Sync + Unpin + 'static
+{}
+
+macro_rules! master_id {
+    ($name:ident) => {
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, sqlx::Type, ToSchema)]
+        #[serde(transparent)]
+        #[sqlx(transparent)]
+        #[schema(value_type = u32)]
+        pub struct $name(pub u32);
+
+        impl From<u32> for $name {
+            fn from(id: u32) -> Self {
+                Self(id)
+            }
+        }
+
+        impl From<$name> for u32 {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                Display::fmt(&self.0, f)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = std::num::ParseIntError;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                value.parse().map(Self)
+            }
+        }
+
+        impl MasterId for $name {}
+    };
+}
+
+master_id!(CountryId);
+master_id!(StateId);
+master_id!(CityId);
+master_id!(ReasonCodeId);
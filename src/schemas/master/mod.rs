@@ -1,3 +1,13 @@
 pub mod city_schema;
 pub mod country_schema;
-pub mod state_schema;
\ No newline at end of file
+pub mod dependent_schema;
+pub mod export_schema;
+pub mod import_schema;
+pub mod list_schema;
+pub mod master_entity;
+pub mod master_id;
+pub mod reason_code_schema;
+pub mod resolve_schema;
+pub mod state_schema;
+pub mod tree_schema;
+pub mod unique_schema;
\ No newline at end of file
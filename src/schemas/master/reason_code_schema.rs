@@ -0,0 +1,197 @@
+use chrono::NaiveDateTime;
+use crate::utils::option::option_ts_seconds;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::schemas::{helper::reason_schema::{ReasonAction, ReasonRequestSchema}, master::{dependent_schema::Dependent, master_entity::{ColumnValue, MasterEntity}, master_id::ReasonCodeId, unique_schema::UniqueRule}};
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ReasonCodeSchema{
+    pub reason_code_id: ReasonCodeId,
+    pub code: String,
+    pub description: String,
+    /// One of `update`, `delete`, `cancel` or `scrap`.
+    pub action: String,
+    pub active: bool,
+    pub created_on: Option<NaiveDateTime>,
+    pub created_by: String,
+
+    #[serde(with = "option_ts_seconds")]
+    pub updated_on: Option<NaiveDateTime>,
+    pub updated_by: Option<String>,
+
+    #[serde(with = "option_ts_seconds")]
+    pub deleted_on: Option<NaiveDateTime>,
+    pub deleted_by: Option<String>
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema)]
+pub struct InsertReasonCodeSchema{
+
+    #[validate(length(min = 2, max = 30, message = "code must be between 2 and 30 characters"))]
+    pub code: String,
+
+    #[validate(length(min = 3, max = 255, message = "description must be between 3 and 255 characters"))]
+    pub description: String,
+
+    pub action: ReasonAction,
+
+    /// Inactive codes are listed but refused on mutations, `true` by default.
+    #[serde(default = "default_active")]
+    pub active: bool,
+
+    #[validate(required)]
+    pub created_by: Option<String>
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize, ToSchema)]
+pub struct UpdateReasonCodeSchema{
+
+    #[validate(length(min = 2, max = 30, message = "code must be between 2 and 30 characters"))]
+    pub code: String,
+
+    #[validate(length(min = 3, max = 255, message = "description must be between 3 and 255 characters"))]
+    pub description: String,
+
+    pub action: ReasonAction,
+
+    pub active: bool,
+
+    #[validate(required)]
+    pub updated_by: Option<String>,
+
+    /// Free text, optional once a valid `reason_code` is given.
+    pub reason: Option<String>,
+
+    /// Active `update` code of the catalog justifying this change.
+    pub reason_code: Option<String>
+}
+
+fn default_active() -> bool {
+    true
+}
+
+#[derive(Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct ReasonCodeDescriptionQuery {
+    pub description: String,
+    pub action: ReasonAction,
+    /// Id of the record being edited, it does not count as a duplicate.
+    pub exclude_id: Option<u32>,
+}
+
+#[derive(Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct ReasonCodeCodeQuery {
+    pub code: String,
+    /// Id of the record being edited, it does not count as a duplicate.
+    pub exclude_id: Option<u32>,
+}
+
+pub struct ReasonCode;
+
+impl MasterEntity for ReasonCode {
+    const LABEL: &'static str = "Reason code";
+    const PATH: &'static str = "reason-codes";
+    const TABLE: &'static str = "precise.reason_code";
+    const ID_COLUMN: &'static str = "reason_code_id";
+    const CODE_COLUMN: &'static str = "code";
+    const NAME_COLUMN: &'static str = "description";
+    const SELECT: &'static str = "select m.reason_code_id, m.code, m.description, cast(m.action as char) as action, m.active,
+        m.created_on, m.created_by, m.updated_on, m.updated_by, m.deleted_on, m.deleted_by
+        from precise.reason_code m";
+    const SORT_FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("reason_code_id", "m.reason_code_id"),
+        ("code", "m.code"),
+        ("description", "m.description"),
+        ("action", "m.action"),
+        ("created_on", "m.created_on"),
+        ("updated_on", "m.updated_on"),
+    ];
+    const FILTER_FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("action", "m.action"),
+        ("active", "if(m.active, 'true', 'false')"),
+    ];
+    const DEPENDENTS: &'static [Dependent] = &[];
+    const UNIQUE: &'static [UniqueRule] = &[
+        UniqueRule{ field: "code", key: "code_key", scope: &[] },
+        UniqueRule{ field: "description", key: "description_key", scope: &["action"] },
+    ];
+    const AUDIT_COLUMNS: &'static [&'static str] = &["code", "description", "action", "active", "deleted_on", "deleted_by"];
+
+    type Id = ReasonCodeId;
+    type Row = ReasonCodeSchema;
+    type Insert = InsertReasonCodeSchema;
+    type Update = UpdateReasonCodeSchema;
+
+    fn insert_values(data: &InsertReasonCodeSchema) -> Vec<(&'static str, ColumnValue)> {
+        vec![
+            ("code", ColumnValue::Text(Some(data.code.clone()))),
+            ("description", ColumnValue::Text(Some(data.description.clone()))),
+            ("action", ColumnValue::Text(Some(data.action.as_str().to_string()))),
+            ("active", ColumnValue::Flag(Some(data.active))),
+            ("created_by", ColumnValue::Text(data.created_by.clone())),
+        ]
+    }
+
+    fn update_values(data: &UpdateReasonCodeSchema) -> Vec<(&'static str, ColumnValue)> {
+        vec![
+            ("code", ColumnValue::Text(Some(data.code.clone()))),
+            ("description", ColumnValue::Text(Some(data.description.clone()))),
+            ("action", ColumnValue::Text(Some(data.action.as_str().to_string()))),
+            ("active", ColumnValue::Flag(Some(data.active))),
+            ("updated_by", ColumnValue::Text(data.updated_by.clone())),
+        ]
+    }
+
+    fn insert_actor(data: &InsertReasonCodeSchema) -> Option<&str> {
+        data.created_by.as_deref()
+    }
+
+    fn update_reason(data: &UpdateReasonCodeSchema) -> ReasonRequestSchema {
+        ReasonRequestSchema{
+            reason: data.reason.clone().unwrap_or_default(),
+            updated_by: data.updated_by.clone(),
+            deleted_by: None,
+            reason_code: data.reason_code.clone()
+        }
+    }
+
+    fn row_id(row: &ReasonCodeSchema) -> ReasonCodeId { row.reason_code_id }
+    fn row_code(row: &ReasonCodeSchema) -> &str { &row.code }
+    fn row_name(row: &ReasonCodeSchema) -> &str { &row.description }
+
+    fn row_deleted(row: &ReasonCodeSchema) -> bool { row.deleted_on.is_some() }
+
+    fn set_deleted(row: &mut ReasonCodeSchema, deleted_by: Option<String>) {
+        row.deleted_on = deleted_by.as_ref().map(|_| chrono::Utc::now().naive_utc());
+        row.deleted_by = deleted_by;
+    }
+
+    fn new_row(id: ReasonCodeId, data: &InsertReasonCodeSchema) -> ReasonCodeSchema {
+        ReasonCodeSchema{
+            reason_code_id: id,
+            code: data.code.clone(),
+            description: data.description.clone(),
+            action: data.action.as_str().to_string(),
+            active: data.active,
+            created_on: Some(chrono::Utc::now().naive_utc()),
+            created_by: data.created_by.clone().unwrap_or_default(),
+            updated_on: None,
+            updated_by: None,
+            deleted_on: None,
+            deleted_by: None
+        }
+    }
+
+    fn apply_update(row: &mut ReasonCodeSchema, data: &UpdateReasonCodeSchema) {
+        row.code = data.code.clone();
+        row.description = data.description.clone();
+        row.action = data.action.as_str().to_string();
+        row.active = data.active;
+        row.updated_on = Some(chrono::Utc::now().naive_utc());
+        row.updated_by = data.updated_by.clone();
+    }
+}
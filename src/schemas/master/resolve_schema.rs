@@ -0,0 +1,18 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct ResolveCodesSchema{
+    #[validate(length(min = 1, max = 500, message = "codes must hold between 1 and 500 entries"))]
+    pub codes: Vec<String>
+}
+
+/// Ids keyed by the code as it was requested, codes without a record are listed in `missing`.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ResolvedCodesSchema{
+    pub ids: BTreeMap<String, u32>,
+    pub missing: Vec<String>
+}
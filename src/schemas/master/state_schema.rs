@@ -3,22 +3,27 @@ use serde::{Deserialize, Serialize};
 use crate::utils::option::option_ts_seconds;
 use sqlx::prelude::FromRow;
 use utoipa::{IntoParams, ToSchema};
-use validator::{Validate, ValidationError};
+use validator::Validate;
 
-use crate::{schemas::api_schemas::ApiResponse, settings::db_pool};
-#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
+use crate::schemas::{helper::reason_schema::ReasonRequestSchema, master::{city_schema::City, country_schema::Country, dependent_schema::Dependent, master_entity::{ColumnValue, ImportParent, MasterEntity, Reference}, master_id::{CountryId, StateId}, unique_schema::UniqueRule}};
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct StatesSchema{
-    pub state_id: u8,
+    pub state_id: StateId,
     pub state_code: String,
     pub state_name: String,
-    pub country_id: u8,
+    pub country_id: CountryId,
     pub country_name: String,
     pub created_on: Option<NaiveDateTime>,
     pub created_by: String,
     
     #[serde(with = "option_ts_seconds")]
     pub updated_on: Option<NaiveDateTime>,
-    pub updated_by: Option<String>
+    pub updated_by: Option<String>,
+
+    #[serde(with = "option_ts_seconds")]
+    pub deleted_on: Option<NaiveDateTime>,
+    pub deleted_by: Option<String>
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow, Validate, ToSchema)]
@@ -29,7 +34,7 @@ pub struct InsertStatesSchema{
     #[validate(length(min = 3, message = "state name must be at least 3 characters"))]
     pub state_name: String,
     
-    pub country_id: u8,
+    pub country_id: CountryId,
 
     #[validate(required)]
     pub created_by: Option<String>
@@ -43,133 +48,162 @@ pub struct UpdateStatesSchema{
     #[validate(length(min = 3, message = "state name must be at least 3 characters"))]
     pub state_name: String,
     
-    pub country_id: u8,
-    pub updated_by: String
+    pub country_id: CountryId,
+
+    #[validate(required)]
+    pub updated_by: Option<String>,
+
+    /// Free text, optional once a valid `reason_code` is given.
+    pub reason: Option<String>,
+
+    /// Active `update` code of the reason code catalog justifying this change.
+    pub reason_code: Option<String>
 }
 
 #[derive(Deserialize, Serialize, FromRow, IntoParams, ToSchema)]
 #[into_params(parameter_in = Query)]
 pub struct StateNameQuery {
     pub state_name: String,
+    /// Country the name must be unique within.
+    pub country_id: CountryId,
+    /// Id of the record being edited, it does not count as a duplicate.
+    pub exclude_id: Option<u32>,
 }
 
 #[derive(Deserialize, Serialize, FromRow, IntoParams, ToSchema)]
 #[into_params(parameter_in = Query)]
 pub struct StateCodeQuery {
     pub state_code: String,
+    /// Id of the record being edited, it does not count as a duplicate.
+    pub exclude_id: Option<u32>,
 }
 
-pub async fn validate_state_id(state_id: u8) -> Result<(), ValidationError> {
-    let pool = db_pool().await;
-    let data = sqlx::query_scalar::<_, i64>("select count(*) from precise.state where state_id=?")
-        .bind(&state_id)
-        .fetch_one(&pool)
-        .await
-        .map(|count| count > 0)
-        .unwrap_or(false);
-
-    if data{
-        Ok(())
-    } else {
-        let mut err = ValidationError::new("state_id_not_found");
-        err.message = Some("State ID not found".into());
-        Err(err)
+pub struct State;
+
+impl MasterEntity for State {
+    const LABEL: &'static str = "State";
+    const PATH: &'static str = "states";
+    const TABLE: &'static str = "precise.state";
+    const ID_COLUMN: &'static str = "state_id";
+    const CODE_COLUMN: &'static str = "state_code";
+    const NAME_COLUMN: &'static str = "state_name";
+    const SELECT: &'static str = "select m.state_id, m.state_code, m.state_name, m.country_id, c.country_name,
+        m.created_on, m.created_by, m.updated_on, m.updated_by, m.deleted_on, m.deleted_by
+        from precise.state m
+        left join precise.country c on m.country_id = c.country_id";
+    const SORT_FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("state_id", "m.state_id"),
+        ("state_code", "m.state_code"),
+        ("state_name", "m.state_name"),
+        ("country_name", "c.country_name"),
+        ("created_on", "m.created_on"),
+        ("updated_on", "m.updated_on"),
+    ];
+    const FILTER_FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("country_id", "m.country_id"),
+    ];
+    const DEPENDENTS: &'static [Dependent] = &[
+        Dependent{
+            label: City::LABEL,
+            table: City::TABLE,
+            id_column: City::ID_COLUMN,
+            code_column: City::CODE_COLUMN,
+            name_column: City::NAME_COLUMN,
+            parent_column: "state_id",
+            audit_columns: City::AUDIT_COLUMNS,
+            dependents: City::DEPENDENTS
+        },
+    ];
+    const UNIQUE: &'static [UniqueRule] = &[
+        UniqueRule{ field: "state_code", key: "state_code_key", scope: &[] },
+        UniqueRule{ field: "state_name", key: "state_name_key", scope: &["country_id"] },
+    ];
+    const AUDIT_COLUMNS: &'static [&'static str] = &["state_code", "state_name", "country_id", "deleted_on", "deleted_by"];
+
+    type Id = StateId;
+    type Row = StatesSchema;
+    type Insert = InsertStatesSchema;
+    type Update = UpdateStatesSchema;
+
+    fn insert_values(data: &InsertStatesSchema) -> Vec<(&'static str, ColumnValue)> {
+        vec![
+            ("state_code", ColumnValue::Text(Some(data.state_code.clone()))),
+            ("state_name", ColumnValue::Text(Some(data.state_name.clone()))),
+            ("country_id", ColumnValue::Id(Some(data.country_id.into()))),
+            ("created_by", ColumnValue::Text(data.created_by.clone())),
+        ]
     }
-}
 
-pub async fn validate_state_code(state_code: &str) -> Result<(), ValidationError> {
-    let pool = db_pool().await;
-    let data = sqlx::query_scalar::<_, i64>("select count(*) from precise.state where state_code=?")
-        .bind(&state_code)
-        .fetch_one(&pool)
-        .await
-        .map(|count| count == 0)
-        .unwrap_or(false);
-
-    if data{
-        Ok(())
-    } else {
-        let mut err = ValidationError::new("state_code_exists");
-        err.message = Some("State code already exists".into());
-        Err(err)
+    fn update_values(data: &UpdateStatesSchema) -> Vec<(&'static str, ColumnValue)> {
+        vec![
+            ("state_code", ColumnValue::Text(Some(data.state_code.clone()))),
+            ("state_name", ColumnValue::Text(Some(data.state_name.clone()))),
+            ("country_id", ColumnValue::Id(Some(data.country_id.into()))),
+            ("updated_by", ColumnValue::Text(data.updated_by.clone())),
+        ]
+    }
+
+    fn insert_references(data: &InsertStatesSchema) -> Vec<Reference> {
+        vec![Reference::to::<Country>("country_id", data.country_id)]
+    }
+
+    fn update_references(data: &UpdateStatesSchema) -> Vec<Reference> {
+        vec![Reference::to::<Country>("country_id", data.country_id)]
+    }
+
+    fn row_references(row: &StatesSchema) -> Vec<Reference> {
+        vec![Reference::to::<Country>("country_id", row.country_id)]
     }
-}
 
-#[utoipa::path(
-    get,
-    tag = "State",
-    path = "/precise/api/master/states",
-    responses(
-        (status = 200, description="states data retrieved successfully", body = ApiResponse<StatesSchema>),
-        (status = 500, description="Failed to retrieve data", body = ApiResponse<String>)
-    ),
-    security(("bearer_auth" = []))
-)]
-pub fn get_all_states() {}
-
-#[utoipa::path(
-    post,
-    tag = "State",
-    path = "/precise/api/master/states",
-    responses(
-        (status = 200, description="states data retrieved successfully", body = ApiResponse<InsertStatesSchema>),
-        (status = 400, description="Invalid Input", body = ApiResponse<String>),
-        (status = 500, description="Failed to retrieve data", body = ApiResponse<String>)
-    ),
-    security(("bearer_auth" = []))
-)]
-pub fn create_state() {}
-
-#[utoipa::path(
-    get,
-    tag= "State",
-    path = "/precise/api/master/states/{state_id}",
-    responses(
-        (status = 200, description="states data retrieved successfully", body = ApiResponse<StatesSchema>),
-        (status = 404, description="Data not found", body = ApiResponse<String>),
-        (status = 500, description="Failed to retrieve data", body = ApiResponse<String>)
-    ),
-    security(("bearer_auth" = []))
-)]
-pub fn get_state() {}
-
-#[utoipa::path(
-    get,
-    tag = "State",
-    path = "/precise/api/master/states/{state_id}",
-    responses(
-        (status = 200, description="states data retrieved successfully", body = ApiResponse<StatesSchema>),
-        (status = 404, description="Data not found", body = ApiResponse<String>),
-        (status = 500, description="Failed to retrieve data", body = ApiResponse<String>)
-    ),
-    security(("bearer_auth" = []))
-)]
-pub fn update_state() {}
-
-#[utoipa::path(
-    get,
-    tag = "State",
-    path = "/precise/api/master/states/exists/code",
-    params(StateCodeQuery),
-    responses(
-        (status = 200, description="State code exists", body = ApiResponse<String>),
-        (status = 404, description="State code does not exists", body = ApiResponse<String>),
-        (status = 500, description="Failed to check State code", body = ApiResponse<String>)
-    ),
-    security(("bearer_auth" = []))
-)]
-pub fn exists_state_code() {}
-
-#[utoipa::path(
-    get,
-    tag = "Country",
-    path = "/precise/api/master/states/exists/name",
-    params(StateNameQuery),
-    responses(
-        (status = 200, description="State name exists", body = ApiResponse<String>),
-        (status = 404, description="State name does not exist", body = ApiResponse<String>),
-        (status = 500, description="Failed to check State code", body = ApiResponse<String>)
-    ),
-    security(("bearer_auth" = []))
-)]
-pub fn exists_state_name() {}
\ No newline at end of file
+    fn import_parents() -> Vec<ImportParent> {
+        vec![ImportParent::of::<Country>("country_code", "country_id")]
+    }
+
+    fn insert_actor(data: &InsertStatesSchema) -> Option<&str> {
+        data.created_by.as_deref()
+    }
+
+    fn update_reason(data: &UpdateStatesSchema) -> ReasonRequestSchema {
+        ReasonRequestSchema{
+            reason: data.reason.clone().unwrap_or_default(),
+            updated_by: data.updated_by.clone(),
+            deleted_by: None,
+            reason_code: data.reason_code.clone()
+        }
+    }
+
+    fn row_id(row: &StatesSchema) -> StateId { row.state_id }
+    fn row_code(row: &StatesSchema) -> &str { &row.state_code }
+    fn row_name(row: &StatesSchema) -> &str { &row.state_name }
+
+    fn row_deleted(row: &StatesSchema) -> bool { row.deleted_on.is_some() }
+
+    fn set_deleted(row: &mut StatesSchema, deleted_by: Option<String>) {
+        row.deleted_on = deleted_by.as_ref().map(|_| chrono::Utc::now().naive_utc());
+        row.deleted_by = deleted_by;
+    }
+
+    fn new_row(id: StateId, data: &InsertStatesSchema) -> StatesSchema {
+        StatesSchema{
+            state_id: id,
+            state_code: data.state_code.clone(),
+            state_name: data.state_name.clone(),
+            country_id: data.country_id,
+            country_name: String::new(),
+            created_on: Some(chrono::Utc::now().naive_utc()),
+            created_by: data.created_by.clone().unwrap_or_default(),
+            updated_on: None,
+            updated_by: None,
+            deleted_on: None,
+            deleted_by: None
+        }
+    }
+
+    fn apply_update(row: &mut StatesSchema, data: &UpdateStatesSchema) {
+        row.state_code = data.state_code.clone();
+        row.state_name = data.state_name.clone();
+        row.country_id = data.country_id;
+        row.updated_on = Some(chrono::Utc::now().naive_utc());
+        row.updated_by = data.updated_by.clone();
+    }
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::schemas::master::master_id::{CityId, CountryId, StateId};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CountryNode{
+    pub country_id: CountryId,
+    pub country_code: String,
+    pub country_name: String,
+    pub states: Vec<StateNode>
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StateNode{
+    pub state_id: StateId,
+    pub state_code: String,
+    pub state_name: String,
+    pub cities: Vec<CityNode>
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CityNode{
+    pub city_id: CityId,
+    pub city_code: String,
+    pub city_name: String
+}
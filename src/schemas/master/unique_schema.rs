@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+/// `field` may only appear once among the rows sharing the values of `scope`,
+/// e.g. a state name within its country. Values are compared with `normalize`.
+pub struct UniqueRule {
+    pub field: &'static str,
+    /// Column holding `normalize` of `field`, written along with it and unique with `scope`.
+    pub key: &'static str,
+    pub scope: &'static [&'static str],
+}
+
+/// One rule applied to a candidate record, `value` already normalized.
+/// A `None` scope value only matches rows where that column is null.
+pub struct UniqueCheck {
+    pub field: &'static str,
+    pub key: &'static str,
+    pub value: String,
+    pub scope: Vec<(&'static str, Option<String>)>,
+}
+
+impl UniqueCheck {
+    /// Reads the field and scope of `rule` from a serialized record, `None` when the
+    /// record has no text value for the field.
+    pub fn from_record(rule: &UniqueRule, record: &Value) -> Option<Self> {
+        let value = record.get(rule.field)?.as_str()?;
+
+        Some(UniqueCheck{
+            field: rule.field,
+            key: rule.key,
+            value: normalize(value),
+            scope: rule.scope.iter().map(|column| (*column, scope_value(&record[*column]))).collect(),
+        })
+    }
+
+    /// Reads the field and every scope column of `rule` from query parameters.
+    pub fn from_query(rule: &UniqueRule, params: &HashMap<String, String>) -> Result<Self, String> {
+        let value = params.get(rule.field).ok_or_else(|| format!("{} is required", rule.field))?;
+        let scope = rule.scope.iter()
+            .map(|column| params.get(*column)
+                .map(|value| (*column, Some(value.clone())))
+                .ok_or_else(|| format!("{} is required to check {}", column, rule.field)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(UniqueCheck{ field: rule.field, key: rule.key, value: normalize(value), scope })
+    }
+
+    /// Whether a serialized row holds the same normalized value within the same scope.
+    pub fn matches(&self, row: &Value) -> bool {
+        row.get(self.field).and_then(Value::as_str).is_some_and(|value| normalize(value) == self.value)
+            && self.scope.iter().all(|(column, value)| scope_value(&row[*column]) == *value)
+    }
+}
+
+/// Trims, collapses inner whitespace and lowercases, so `" New  York"` equals `"new york"`.
+pub fn normalize(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+fn scope_value(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(text) => Some(text.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const STATE_NAME: UniqueRule = UniqueRule{ field: "state_name", key: "state_name_key", scope: &["country_id"] };
+
+    #[test]
+    fn normalize_trims_collapses_and_lowercases() {
+        assert_eq!(normalize(" New  York "), "new york");
+        assert_eq!(normalize("new\t\nyork"), "new york");
+        assert_eq!(normalize("ÉCOLE\u{3000}Côte"), "école côte");
+        assert_eq!(normalize("   "), "");
+    }
+
+    #[test]
+    fn checks_match_within_their_scope() {
+        let check = UniqueCheck::from_record(&STATE_NAME, &json!({"state_name": "  West   JAVA", "country_id": 1})).unwrap();
+
+        assert_eq!((check.key, check.value.as_str()), ("state_name_key", "west java"));
+        assert!(check.matches(&json!({"state_name": "west java", "country_id": 1})));
+        assert!(!check.matches(&json!({"state_name": "west java", "country_id": 2})));
+        assert!(!check.matches(&json!({"state_name": "west-java", "country_id": 1})));
+        assert!(UniqueCheck::from_record(&STATE_NAME, &json!({"country_id": 1})).is_none());
+    }
+
+    #[test]
+    fn query_checks_need_every_scope_column() {
+        let params = |pairs: &[(&str, &str)]| pairs.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect::<HashMap<_, _>>();
+
+        assert_eq!(UniqueCheck::from_query(&STATE_NAME, &params(&[("state_name", "Bali")])).err().unwrap(), "country_id is required to check state_name");
+        assert_eq!(UniqueCheck::from_query(&STATE_NAME, &params(&[("country_id", "1")])).err().unwrap(), "state_name is required");
+
+        let check = UniqueCheck::from_query(&STATE_NAME, &params(&[("state_name", "Bali "), ("country_id", "1")])).unwrap();
+        assert!(check.matches(&json!({"state_name": "BALI", "country_id": 1})));
+    }
+}
@@ -1,4 +1,6 @@
+pub mod audit;
 pub mod auth;
+pub mod health;
 pub mod master;
 pub mod helper;
 pub mod api_schemas;
\ No newline at end of file
@@ -0,0 +1,132 @@
+use std::{env, fmt, path::PathBuf};
+
+use config::{Config, ConfigError, Environment as EnvSource, File};
+use dotenv::dotenv;
+use serde::Deserialize;
+
+use crate::settings::{DatabaseSettings, JwtSecret, Secrets};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Settings{
+    pub application: ApplicationSettings,
+    pub database: DatabaseSettings,
+
+    #[serde(default)]
+    pub reason: ReasonSettings,
+
+    #[serde(skip)]
+    pub environment: Environment,
+
+    #[serde(skip)]
+    pub jwt_secret: JwtSecret
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApplicationSettings{
+    pub host: String,
+    pub port: u16,
+
+    /// Apply pending migrations before the server starts listening.
+    #[serde(default)]
+    pub run_migrations: bool
+}
+
+impl ApplicationSettings{
+    pub fn address(&self) -> (String, u16) {
+        (self.host.clone(), self.port)
+    }
+}
+
+/// Rules for the reason every update, delete and restore must carry.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReasonSettings{
+    /// Characters required once surrounding whitespace is trimmed.
+    #[serde(default = "default_reason_min_length")]
+    pub min_length: usize,
+
+    /// Refuse free text alone, every change must name a code of the reason code catalog.
+    #[serde(default)]
+    pub require_code: bool
+}
+
+impl Default for ReasonSettings{
+    fn default() -> Self {
+        Self { min_length: default_reason_min_length(), require_code: false }
+    }
+}
+
+fn default_reason_min_length() -> usize {
+    1
+}
+
+/// Profile selected with `APP_ENVIRONMENT`, maps to `config/{profile}.toml`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Environment{
+    #[default]
+    Dev,
+    Prod
+}
+
+impl Environment{
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Environment::Dev => "dev",
+            Environment::Prod => "prod",
+        }
+    }
+
+    pub fn current() -> Result<Self, ConfigError> {
+        env::var("APP_ENVIRONMENT")
+            .unwrap_or_else(|_| "dev".into())
+            .try_into()
+    }
+}
+
+impl fmt::Display for Environment{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl TryFrom<String> for Environment{
+    type Error = ConfigError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "dev" | "development" | "local" => Ok(Environment::Dev),
+            "prod" | "production" => Ok(Environment::Prod),
+            other => Err(ConfigError::Message(format!(
+                "{} is not a supported environment, use either `dev` or `prod`", other
+            ))),
+        }
+    }
+}
+
+/// Loads `config/{profile}.toml` and applies `APP_*` overrides on top,
+/// e.g. `APP_DATABASE__HOST=10.0.0.5` or `APP_APPLICATION__PORT=9000`.
+/// Fails when any secret from [`Secrets`] cannot be resolved.
+pub fn get_configuration() -> Result<Settings, ConfigError> {
+    dotenv().ok();
+    let environment = Environment::current()?;
+    let config_dir = env::var("APP_CONFIG_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("config"));
+
+    let mut settings = Config::builder()
+        .add_source(File::from(config_dir.join(format!("{}.toml", environment.as_str()))))
+        .add_source(
+            EnvSource::with_prefix("APP")
+                .prefix_separator("_")
+                .separator("__")
+                .try_parsing(true)
+        )
+        .build()?
+        .try_deserialize::<Settings>()?;
+
+    let secrets = Secrets::load().map_err(|err| ConfigError::Message(err.to_string()))?;
+    settings.environment = environment;
+    settings.database.password = secrets.database_password;
+    settings.jwt_secret = secrets.jwt_secret;
+
+    Ok(settings)
+}
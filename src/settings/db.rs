@@ -1,17 +1,31 @@
-use std::env;
+use std::fmt;
 
+use serde::Deserialize;
 use sqlx::{mysql::MySqlConnectOptions, MySqlPool};
-use dotenv::dotenv;
 
-#[derive(Debug)]
+#[derive(Clone, Deserialize)]
 pub struct DatabaseSettings{
     pub username: String,
+
+    #[serde(skip)]
     pub password: String,
     pub port: u16,
     pub host: String,
     pub database_name: String
 }
 
+impl fmt::Debug for DatabaseSettings{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DatabaseSettings")
+            .field("username", &self.username)
+            .field("password", &"***")
+            .field("port", &self.port)
+            .field("host", &self.host)
+            .field("database_name", &self.database_name)
+            .finish()
+    }
+}
+
 impl DatabaseSettings{
     pub fn parse_conn_string(&self) -> MySqlConnectOptions {
         MySqlConnectOptions::new()
@@ -26,9 +40,6 @@ impl DatabaseSettings{
     }
 }
 
-pub async fn db_pool() -> MySqlPool {
-    dotenv().ok();
-    let db_url = env::var("DATABASE_URL").expect("Failed to get settings database");
-    let connection = MySqlPool::connect(&db_url).await.expect("Cannot connect to database");
-    connection
-}
\ No newline at end of file
+pub async fn connect_pool(settings: &DatabaseSettings) -> MySqlPool {
+    MySqlPool::connect_with(settings.db_conn_string()).await.expect("Cannot connect to database")
+}
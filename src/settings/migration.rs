@@ -0,0 +1,58 @@
+use std::collections::HashSet;
+
+use sqlx::{migrate::{Migrate, MigrateError, Migration, Migrator}, MySqlPool};
+
+/// Versioned SQL files from `migrations/`, embedded at compile time.
+/// Applied versions are tracked in the `_sqlx_migrations` history table.
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+pub async fn pending_migrations(pool: &MySqlPool) -> Result<Vec<&'static Migration>, MigrateError> {
+    let history_exists = sqlx::query_scalar::<_, i64>(
+        "select count(*) from information_schema.tables
+        where table_schema = database() and table_name = '_sqlx_migrations'")
+        .fetch_one(pool)
+        .await?
+        > 0;
+
+    let applied: HashSet<i64> = if history_exists {
+        let mut conn = pool.acquire().await?;
+        conn.list_applied_migrations()
+            .await?
+            .into_iter()
+            .map(|migration| migration.version)
+            .collect()
+    } else {
+        HashSet::new()
+    };
+
+    Ok(MIGRATOR
+        .iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+        .filter(|migration| !applied.contains(&migration.version))
+        .collect())
+}
+
+/// Applies every pending migration, or only reports them when `dry_run` is set.
+pub async fn run_migrations(pool: &MySqlPool, dry_run: bool) -> Result<Vec<&'static Migration>, MigrateError> {
+    let pending = pending_migrations(pool).await?;
+
+    if !dry_run && !pending.is_empty() {
+        MIGRATOR.run(pool).await?;
+    }
+
+    Ok(pending)
+}
+
+/// `run_migrations` for the `migrate` commands, printing one line per migration.
+pub async fn migrate(pool: &MySqlPool, dry_run: bool) -> Result<(), MigrateError> {
+    let migrations = run_migrations(pool, dry_run).await?;
+
+    if migrations.is_empty() {
+        println!("Database schema is up to date");
+    }
+    for migration in migrations {
+        let status = if dry_run { "pending" } else { "applied" };
+        println!("{} {} {}", status, migration.version, migration.description);
+    }
+    Ok(())
+}
@@ -1,3 +1,9 @@
+mod configuration;
 mod db;
+mod migration;
+mod secrets;
 
-pub use db::*;
\ No newline at end of file
+pub use configuration::*;
+pub use db::*;
+pub use migration::*;
+pub use secrets::*;
\ No newline at end of file
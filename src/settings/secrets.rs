@@ -0,0 +1,102 @@
+use std::{env, fmt, fs};
+
+/// Secrets never live in `config/*.toml`. Each one is read from `NAME_FILE`
+/// (a mounted Docker/K8s secret) when set, otherwise from `NAME`. The database
+/// password may be empty, as for a local `root` account; the JWT secret may not.
+pub struct Secrets{
+    pub database_password: String,
+    pub jwt_secret: JwtSecret
+}
+
+impl Secrets{
+    pub fn load() -> Result<Self, SecretsError> {
+        let mut error = SecretsError::default();
+
+        let database_password = resolve("DATABASE_PASSWORD", true, &mut error);
+        let jwt_secret = resolve("JWT_SECRET", false, &mut error);
+
+        match (database_password, jwt_secret) {
+            (Some(database_password), Some(jwt_secret)) if error.is_empty() => Ok(Secrets{
+                database_password,
+                jwt_secret: JwtSecret::new(jwt_secret)
+            }),
+            _ => Err(error),
+        }
+    }
+}
+
+/// A secret that is set but empty only counts as missing when `allow_empty` is false.
+fn resolve(name: &str, allow_empty: bool, error: &mut SecretsError) -> Option<String> {
+    let file_var = format!("{}_FILE", name);
+
+    if let Ok(path) = env::var(&file_var) {
+        return match fs::read_to_string(&path) {
+            Ok(value) if allow_empty || !value.trim().is_empty() => Some(value.trim().to_string()),
+            Ok(_) => {
+                error.missing.push(format!("{} ({} is empty)", name, path));
+                None
+            },
+            Err(err) => {
+                error.unreadable.push(format!("{} ({}: {})", file_var, path, err));
+                None
+            }
+        };
+    }
+
+    match env::var(name) {
+        Ok(value) if allow_empty || !value.is_empty() => Some(value),
+        _ => {
+            error.missing.push(format!("{} (or {})", name, file_var));
+            None
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SecretsError{
+    pub missing: Vec<String>,
+    pub unreadable: Vec<String>
+}
+
+impl SecretsError{
+    fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.unreadable.is_empty()
+    }
+}
+
+impl fmt::Display for SecretsError{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.missing.is_empty() {
+            write!(f, "missing secrets: {}", self.missing.join(", "))?;
+        }
+        if !self.unreadable.is_empty() {
+            if !self.missing.is_empty() {
+                f.write_str("; ")?;
+            }
+            write!(f, "unreadable secret files: {}", self.unreadable.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for SecretsError {}
+
+/// Signing key shared by `JwtMiddleware` and the login handler.
+#[derive(Clone, Default)]
+pub struct JwtSecret(String);
+
+impl JwtSecret{
+    pub fn new(secret: String) -> Self{
+        Self(secret)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl fmt::Debug for JwtSecret{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("JwtSecret(***)")
+    }
+}
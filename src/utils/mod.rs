@@ -1 +1,2 @@
-pub mod option;
\ No newline at end of file
+pub mod option;
+pub mod validated_json;
\ No newline at end of file
@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use rust_precise::repositories::master::master_repository::master_repository::{InMemoryMasterRepository, MasterRepository};
+use rust_precise::schemas::helper::reason_schema::ReasonRequestSchema;
+use rust_precise::schemas::master::country_schema::{Country, InsertCountrySchema};
+use rust_precise::schemas::master::dependent_schema::{DeleteOutcome, RestoreOutcome};
+use rust_precise::schemas::master::import_schema::ImportChange;
+use rust_precise::schemas::master::list_schema::ListQuery;
+use rust_precise::schemas::master::master_id::{CountryId, StateId};
+use rust_precise::schemas::master::state_schema::{InsertStatesSchema, State, StatesSchema, UpdateStatesSchema};
+use rust_precise::schemas::master::unique_schema::UniqueCheck;
+use validator::ValidationErrors;
+
+fn insert(code: &str, name: &str, country_id: u32) -> InsertStatesSchema {
+    InsertStatesSchema{ state_code: code.into(), state_name: name.into(), country_id: CountryId(country_id), created_by: Some("tester".into()) }
+}
+
+fn update(code: &str, name: &str, country_id: u32) -> UpdateStatesSchema {
+    serde_json::from_value(serde_json::json!({
+        "state_code": code, "state_name": name, "country_id": country_id, "updated_by": "editor", "reason": "correction"
+    })).unwrap()
+}
+
+fn reason(deleted_by: &str) -> ReasonRequestSchema {
+    ReasonRequestSchema{ reason: "correction".into(), updated_by: Some(deleted_by.into()), deleted_by: Some(deleted_by.into()), reason_code: None }
+}
+
+fn list(pairs: &[(&str, &str)]) -> ListQuery {
+    let params: HashMap<String, String> = pairs.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect();
+    ListQuery::parse::<State>(&params).unwrap()
+}
+
+/// West Java, Bali and Central Java in country 1, Selangor in country 2.
+async fn repository() -> InMemoryMasterRepository<State> {
+    let repository = InMemoryMasterRepository::<State>::default();
+    for data in [insert("JBR", "West Java", 1), insert("BAL", "Bali", 1), insert("JTG", "Central Java", 1), insert("SGR", "Selangor", 2)] {
+        repository.insert(&data).await.unwrap();
+    }
+    repository
+}
+
+fn codes(rows: &[StatesSchema]) -> Vec<&str> {
+    rows.iter().map(|row| row.state_code.as_str()).collect()
+}
+
+#[actix_web::test]
+async fn insert_numbers_rows_and_finds_them() {
+    let repository = repository().await;
+
+    assert_eq!(repository.insert(&insert("KLT", "Kelantan", 2)).await.unwrap(), StateId(5));
+    assert_eq!(repository.find_by_id(StateId(2)).await.unwrap().state_name, "Bali");
+    assert_eq!(repository.find_by_code("SGR").await.unwrap().state_id, StateId(4));
+    assert!(matches!(repository.find_by_id(StateId(9)).await, Err(sqlx::Error::RowNotFound)));
+    assert_eq!(repository.resolve_codes(&["BAL".into(), "XXX".into()]).await.unwrap(), vec![("BAL".to_string(), StateId(2))]);
+}
+
+#[actix_web::test]
+async fn find_page_filters_searches_and_pages() {
+    let repository = repository().await;
+
+    let (rows, total) = repository.find_page(&list(&[("country_id", "1"), ("sort", "state_name")])).await.unwrap();
+    assert_eq!((codes(&rows), total), (vec!["BAL", "JTG", "JBR"], 3));
+
+    let (rows, total) = repository.find_page(&list(&[("q", "java"), ("per_page", "1"), ("page", "2")])).await.unwrap();
+    assert_eq!((codes(&rows), total), (vec!["JTG"], 2));
+
+    let (rows, _) = repository.find_page(&list(&[("cursor", "2"), ("per_page", "2")])).await.unwrap();
+    assert_eq!(codes(&rows), vec!["JTG", "SGR"]);
+}
+
+#[actix_web::test]
+async fn deleted_rows_are_hidden_and_left_alone() {
+    let repository = repository().await;
+
+    assert!(matches!(repository.soft_delete(StateId(2), &reason("editor"), false).await.unwrap(), DeleteOutcome::Deleted));
+    assert!(matches!(repository.soft_delete(StateId(2), &reason("editor"), false).await.unwrap(), DeleteOutcome::NotChanged));
+    assert!(matches!(repository.soft_delete(StateId(9), &reason("editor"), true).await.unwrap(), DeleteOutcome::NotChanged));
+
+    assert_eq!(codes(&repository.find_all().await.unwrap()), vec!["JBR", "JTG", "SGR"]);
+    assert_eq!(repository.find_page(&list(&[("include_deleted", "true")])).await.unwrap().1, 4);
+    assert_eq!(repository.find_by_id(StateId(2)).await.unwrap().deleted_by.as_deref(), Some("editor"));
+    assert!(repository.exists_id(StateId(2)).await.unwrap());
+
+    assert_eq!(repository.update(StateId(2), &update("BAL", "Bali Island", 1)).await.unwrap(), 0);
+    assert_eq!(repository.find_by_id(StateId(2)).await.unwrap().state_name, "Bali");
+
+    assert!(matches!(repository.restore(StateId(2), &reason("editor")).await.unwrap(), RestoreOutcome::Restored));
+    assert!(matches!(repository.restore(StateId(2), &reason("editor")).await.unwrap(), RestoreOutcome::NotChanged));
+    assert_eq!(repository.update(StateId(2), &update("BAL", "Bali Island", 1)).await.unwrap(), 1);
+    assert_eq!(repository.find_by_id(StateId(2)).await.unwrap().state_name, "Bali Island");
+}
+
+#[actix_web::test]
+async fn rows_under_a_deleted_parent_stay_deleted() {
+    let countries = Arc::new(InMemoryMasterRepository::<Country>::default());
+    let indonesia = InsertCountrySchema{ country_code: "IDN".into(), country_name: "Indonesia".into(), created_by: Some("tester".into()) };
+    let country_id = countries.insert(&indonesia).await.unwrap();
+    let repository = InMemoryMasterRepository::<State>::default().with_parent(countries.clone());
+    let state_id = repository.insert(&insert("JBR", "West Java", country_id.into())).await.unwrap();
+
+    repository.soft_delete(state_id, &reason("editor"), false).await.unwrap();
+    countries.soft_delete(country_id, &reason("editor"), false).await.unwrap();
+
+    match repository.restore(state_id, &reason("editor")).await.unwrap() {
+        RestoreOutcome::ParentDeleted(parent) => assert_eq!((parent.field, parent.label, parent.id), ("country_id", "Country", 1)),
+        _ => panic!("restored under a deleted country"),
+    }
+    assert!(repository.find_by_id(state_id).await.unwrap().deleted_on.is_some());
+
+    countries.restore(country_id, &reason("editor")).await.unwrap();
+    assert!(matches!(repository.restore(state_id, &reason("editor")).await.unwrap(), RestoreOutcome::Restored));
+}
+
+#[actix_web::test]
+async fn unique_rules_are_scoped_and_skip_the_edited_row() {
+    let repository = repository().await;
+
+    let errors = repository.validate_insert(&insert("jbr", " west  JAVA ", 1), ValidationErrors::new()).await.unwrap();
+    let mut fields: Vec<String> = errors.field_errors().keys().map(|field| field.to_string()).collect();
+    fields.sort();
+    assert_eq!(fields, vec!["state_code", "state_name"]);
+
+    let errors = repository.validate_insert(&insert("JBR2", "West Java", 2), ValidationErrors::new()).await.unwrap();
+    assert!(errors.is_empty());
+
+    let errors = repository.validate_update(StateId(1), &update("JBR", "west java", 1), ValidationErrors::new()).await.unwrap();
+    assert!(errors.is_empty());
+
+    let errors = repository.validate_update(StateId(3), &update("JTG", "West Java", 1), ValidationErrors::new()).await.unwrap();
+    assert_eq!(errors.field_errors()["state_name"][0].message.as_deref(), Some("state_name west java is already used for country_id 1"));
+
+    let check = UniqueCheck{ field: "state_name", key: "state_name_key", value: "bali".into(), scope: vec![("country_id", Some("1".into()))] };
+    assert!(repository.is_taken(&check, None).await.unwrap());
+    assert!(!repository.is_taken(&check, Some(StateId(2))).await.unwrap());
+}
+
+#[actix_web::test]
+async fn import_applies_changes_in_order_and_nothing_when_one_fails() {
+    let repository = repository().await;
+
+    let changes = [ImportChange::Update(StateId(1), update("JBR", "Jawa Barat", 1)), ImportChange::Insert(insert("JTM", "East Java", 1))];
+    assert_eq!(repository.import(&changes, &reason("importer")).await.unwrap(), vec![StateId(1), StateId(5)]);
+    assert_eq!(repository.find_by_id(StateId(1)).await.unwrap().state_name, "Jawa Barat");
+
+    repository.soft_delete(StateId(3), &reason("editor"), false).await.unwrap();
+    let changes = [
+        ImportChange::Update(StateId(2), update("BAL", "Pulau Bali", 1)),
+        ImportChange::Insert(insert("NTB", "West Nusa Tenggara", 1)),
+        ImportChange::Update(StateId(3), update("JTG", "Jawa Tengah", 1)),
+    ];
+    assert!(matches!(repository.import(&changes, &reason("importer")).await, Err(sqlx::Error::RowNotFound)));
+    assert_eq!(repository.find_by_id(StateId(2)).await.unwrap().state_name, "Bali");
+    assert!(matches!(repository.find_by_code("NTB").await, Err(sqlx::Error::RowNotFound)));
+}
@@ -0,0 +1,295 @@
+use std::sync::Arc;
+
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::http::{Method, StatusCode};
+use actix_web::{test, web, App};
+use rust_precise::errors::{json_config, path_config, query_config};
+use rust_precise::repositories::master::master_repository::master_repository::{InMemoryMasterRepository, MasterRepository, RepositoryData};
+use rust_precise::routes::master::master_routes::master_routes::master_resource;
+use rust_precise::schemas::master::city_schema::City;
+use rust_precise::schemas::master::country_schema::Country;
+use rust_precise::schemas::master::master_entity::MasterEntity;
+use rust_precise::schemas::master::reason_code_schema::ReasonCode;
+use rust_precise::schemas::master::state_schema::State;
+use rust_precise::settings::ReasonSettings;
+use serde_json::{json, Value};
+
+fn repository<E: MasterEntity>() -> RepositoryData<E> {
+    web::Data::from(Arc::new(InMemoryMasterRepository::<E>::default()) as Arc<dyn MasterRepository<E>>)
+}
+
+/// The `DATA_FIX` update and `DUPLICATE` delete reason codes.
+async fn reason_codes() -> RepositoryData<ReasonCode> {
+    let reason_codes = repository::<ReasonCode>();
+    for (code, action) in [("DATA_FIX", "update"), ("DUPLICATE", "delete")] {
+        let data = serde_json::from_value(json!({"code": code, "description": "seeded code", "action": action, "created_by": "test"})).unwrap();
+        reason_codes.insert(&data).await.unwrap();
+    }
+    reason_codes
+}
+
+/// Countries on an in-memory repository.
+async fn app() -> impl Service<actix_http::Request, Response = ServiceResponse, Error = actix_web::Error> {
+    test::init_service(
+        App::new()
+            .app_data(repository::<Country>())
+            .app_data(reason_codes().await)
+            .app_data(web::Data::new(ReasonSettings{ min_length: 5, require_code: false }))
+            .app_data(json_config())
+            .app_data(query_config())
+            .app_data(path_config())
+            .configure(master_resource::<Country>)
+    ).await
+}
+
+/// Countries, states and cities, with Indonesia as country 1 and references checked against the parent repositories.
+async fn regions_app() -> impl Service<actix_http::Request, Response = ServiceResponse, Error = actix_web::Error> {
+    let countries = Arc::new(InMemoryMasterRepository::<Country>::default());
+    let indonesia = serde_json::from_value(json!({"country_code": "IDN", "country_name": "Indonesia", "created_by": "test"})).unwrap();
+    countries.insert(&indonesia).await.unwrap();
+    let states = Arc::new(InMemoryMasterRepository::<State>::default().with_parent(countries.clone()));
+    let cities = InMemoryMasterRepository::<City>::default().with_parent(states.clone());
+
+    test::init_service(
+        App::new()
+            .app_data(web::Data::from(countries as Arc<dyn MasterRepository<Country>>))
+            .app_data(web::Data::from(states as Arc<dyn MasterRepository<State>>))
+            .app_data(web::Data::from(Arc::new(cities) as Arc<dyn MasterRepository<City>>))
+            .app_data(reason_codes().await)
+            .app_data(web::Data::new(ReasonSettings{ min_length: 5, require_code: false }))
+            .app_data(json_config())
+            .app_data(query_config())
+            .app_data(path_config())
+            .configure(master_resource::<Country>)
+            .configure(master_resource::<State>)
+            .configure(master_resource::<City>)
+    ).await
+}
+
+async fn call(app: &impl Service<actix_http::Request, Response = ServiceResponse, Error = actix_web::Error>, method: Method, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let mut request = test::TestRequest::default().method(method).uri(uri);
+    if let Some(body) = body {
+        request = request.set_json(body);
+    }
+    let response = test::call_service(app, request.to_request()).await;
+    (response.status(), test::read_body_json(response).await)
+}
+
+async fn create(app: &impl Service<actix_http::Request, Response = ServiceResponse, Error = actix_web::Error>, code: &str, name: &str) -> u64 {
+    let (status, body) = call(app, Method::POST, "/countries", Some(json!({"country_code": code, "country_name": name, "created_by": "tester"}))).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    body["data"]["country_id"].as_u64().unwrap()
+}
+
+#[actix_web::test]
+async fn create_then_read_back() {
+    let app = app().await;
+    let id = create(&app, "IDN", "Indonesia").await;
+
+    let (status, body) = call(&app, Method::GET, &format!("/countries/{}", id), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!((body["data"]["country_code"].as_str(), body["data"]["created_by"].as_str()), (Some("IDN"), Some("tester")));
+
+    let (status, body) = call(&app, Method::GET, "/countries/by-code/IDN", None).await;
+    assert_eq!((status, body["data"]["country_id"].as_u64()), (StatusCode::OK, Some(id)));
+
+    let (status, _) = call(&app, Method::GET, "/countries/99", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn create_reports_field_errors() {
+    let app = app().await;
+
+    let (status, body) = call(&app, Method::POST, "/countries", Some(json!({"country_code": "ID", "country_name": "Indonesia"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["data"], json!({"country_code": ["country code must be at least 3 characters"], "created_by": ["required"]}));
+
+    let (status, body) = call(&app, Method::POST, "/countries", Some(json!({"country_code": 1}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["message"].as_str().unwrap().starts_with("invalid JSON body"));
+}
+
+#[actix_web::test]
+async fn field_rules_and_missing_parents_fail_together() {
+    let app = regions_app().await;
+
+    let (status, body) = call(&app, Method::POST, "/states", Some(json!({"state_code": "JB", "state_name": "West Java", "country_id": 9, "created_by": "tester"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["data"], json!({"state_code": ["state code must be at least 3 characters"], "country_id": ["Country 9 does not exist"]}));
+
+    let (status, body) = call(&app, Method::POST, "/states", Some(json!({"state_code": "JBR", "state_name": "West Java", "country_id": 1, "created_by": "tester"}))).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let uri = format!("/states/{}", body["data"]["state_id"]);
+    let (status, body) = call(&app, Method::PUT, &uri, Some(json!({"state_code": "JBR", "state_name": "WJ", "country_id": 9, "updated_by": "editor", "reason": "typo"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["data"], json!({
+        "state_name": ["state name must be at least 3 characters"],
+        "country_id": ["Country 9 does not exist"],
+        "reason": ["reason must be at least 5 characters"],
+    }));
+}
+
+#[actix_web::test]
+async fn state_and_city_updates_check_their_field_rules() {
+    let app = regions_app().await;
+
+    let (_, body) = call(&app, Method::POST, "/states", Some(json!({"state_code": "JBR", "state_name": "West Java", "country_id": 1, "created_by": "tester"}))).await;
+    let state_id = body["data"]["state_id"].as_u64().unwrap();
+    let (status, body) = call(&app, Method::PUT, &format!("/states/{}", state_id), Some(json!({"state_code": "JBR", "state_name": "Jawa Barat", "country_id": 1, "reason": "local name"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["data"], json!({"updated_by": ["required"]}));
+
+    let (_, body) = call(&app, Method::POST, "/cities", Some(json!({"city_code": "BDG", "city_name": "Bandung", "state_id": state_id, "created_by": "tester"}))).await;
+    let uri = format!("/cities/{}", body["data"]["city_id"]);
+    let (status, body) = call(&app, Method::PUT, &uri, Some(json!({"city_code": "BD", "city_name": "Bd", "reason": "short form"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["data"], json!({
+        "city_code": ["city code must be at least 3 characters"],
+        "city_name": ["city name must be at least 3 characters"],
+        "updated_by": ["required"],
+    }));
+
+    let (status, body) = call(&app, Method::PUT, &uri, Some(json!({"city_code": "BDG", "city_name": "Kota Bandung", "updated_by": "editor", "reason": "official name"}))).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["data"]["updated_by"], json!("editor"));
+}
+
+#[actix_web::test]
+async fn restore_refuses_a_row_under_a_deleted_parent() {
+    let app = regions_app().await;
+
+    let (_, body) = call(&app, Method::POST, "/states", Some(json!({"state_code": "JBR", "state_name": "West Java", "country_id": 1, "created_by": "tester"}))).await;
+    let uri = format!("/states/{}", body["data"]["state_id"]);
+    for target in [uri.as_str(), "/countries/1"] {
+        let (status, body) = call(&app, Method::DELETE, target, Some(json!({"deleted_by": "editor", "reason": "merged away"}))).await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+    }
+
+    let (status, body) = call(&app, Method::POST, &format!("{}/restore", uri), Some(json!({"updated_by": "editor", "reason": "deleted by mistake"}))).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["message"], json!("State 1 cannot be restored, Country 1 is deleted"));
+
+    let (status, _) = call(&app, Method::POST, "/countries/1/restore", Some(json!({"updated_by": "editor", "reason": "deleted by mistake"}))).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = call(&app, Method::POST, &format!("{}/restore", uri), Some(json!({"updated_by": "editor", "reason": "deleted by mistake"}))).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+}
+
+#[actix_web::test]
+async fn create_refuses_a_normalized_duplicate() {
+    let app = app().await;
+    create(&app, "IDN", "Indonesia").await;
+
+    let (status, body) = call(&app, Method::POST, "/countries", Some(json!({"country_code": "idn", "country_name": "  INDONESIA ", "created_by": "tester"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["data"], json!({
+        "country_code": ["country_code idn is already used"],
+        "country_name": ["country_name indonesia is already used"],
+    }));
+
+    let (_, body) = call(&app, Method::GET, "/countries/exists/name?country_name=indonesia", None).await;
+    assert_eq!(body["data"], json!(true));
+    let (_, body) = call(&app, Method::GET, "/countries/exists/name?country_name=indonesia&exclude_id=1", None).await;
+    assert_eq!(body["data"], json!(false));
+}
+
+#[actix_web::test]
+async fn list_pages_sorts_and_searches() {
+    let app = app().await;
+    for (code, name) in [("IDN", "Indonesia"), ("JPN", "Japan"), ("KOR", "Korea"), ("MYS", "Malaysia")] {
+        create(&app, code, name).await;
+    }
+
+    let (status, body) = call(&app, Method::GET, "/countries?page=2&per_page=3&sort=-country_code", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"].as_array().unwrap().iter().map(|row| row["country_code"].as_str().unwrap()).collect::<Vec<_>>(), vec!["IDN"]);
+    assert_eq!(body["pagination"], json!({"total": 4, "per_page": 3, "page": 2, "next_cursor": null}));
+
+    let (_, body) = call(&app, Method::GET, "/countries?cursor=0&per_page=2", None).await;
+    assert_eq!(body["pagination"]["next_cursor"], json!(2));
+
+    let (_, body) = call(&app, Method::GET, "/countries?q=KOR", None).await;
+    assert_eq!(body["pagination"]["total"], json!(1));
+
+    let (status, body) = call(&app, Method::GET, "/countries?sort=password", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["message"].as_str().unwrap().starts_with("cannot sort by password"));
+}
+
+#[actix_web::test]
+async fn update_needs_a_reason() {
+    let app = app().await;
+    let id = create(&app, "IDN", "Indonesia").await;
+    let uri = format!("/countries/{}", id);
+
+    let (status, body) = call(&app, Method::PUT, &uri, Some(json!({"country_code": "IDN", "country_name": "Republic of Indonesia", "updated_by": "editor", "reason": "typo"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["data"], json!({"reason": ["reason must be at least 5 characters"]}));
+
+    let (status, body) = call(&app, Method::PUT, &uri, Some(json!({"country_code": "IDN", "country_name": "Republic of Indonesia", "updated_by": "editor", "reason_code": "DUPLICATE"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["data"], json!({"reason_code": ["DUPLICATE is not an active update reason code"]}));
+
+    let (status, body) = call(&app, Method::PUT, &uri, Some(json!({"country_code": "IDN", "country_name": "Republic of Indonesia", "updated_by": "editor", "reason_code": "DATA_FIX"}))).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["data"]["country_name"], json!("Republic of Indonesia"));
+}
+
+#[actix_web::test]
+async fn patch_merges_into_the_stored_record() {
+    let app = app().await;
+    let id = create(&app, "IDN", "Indonesia").await;
+    let uri = format!("/countries/{}", id);
+
+    let (status, body) = call(&app, Method::PATCH, &uri, Some(json!({"country_name": "Republic of Indonesia", "updated_by": "editor", "reason": "official name"}))).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!((body["data"]["country_code"].as_str(), body["data"]["country_name"].as_str()), (Some("IDN"), Some("Republic of Indonesia")));
+
+    let (status, body) = call(&app, Method::PATCH, &uri, Some(json!({"country_name": "Indonesia", "reason": "official name"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["data"], json!({"updated_by": ["required"]}));
+
+    let (status, _) = call(&app, Method::PATCH, &uri, Some(json!(["country_name"]))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn delete_and_restore() {
+    let app = app().await;
+    let id = create(&app, "IDN", "Indonesia").await;
+    let uri = format!("/countries/{}", id);
+
+    let (status, body) = call(&app, Method::DELETE, &uri, Some(json!({"reason": "entered twice"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["data"], json!({"deleted_by": ["required"]}));
+
+    let (status, body) = call(&app, Method::DELETE, &uri, Some(json!({"deleted_by": "editor", "reason_code": "DUPLICATE"}))).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["data"]["deleted_by"], json!("editor"));
+
+    let (status, _) = call(&app, Method::DELETE, &uri, Some(json!({"deleted_by": "editor", "reason_code": "DUPLICATE"}))).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, body) = call(&app, Method::PUT, &uri, Some(json!({"country_code": "IDN", "country_name": "Indonesia", "updated_by": "editor", "reason": "rename it"}))).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["message"], json!(format!("Country {} is deleted, restore it first", id)));
+
+    let (_, body) = call(&app, Method::GET, "/countries", None).await;
+    assert_eq!(body["pagination"]["total"], json!(0));
+
+    let (status, body) = call(&app, Method::POST, &format!("{}/restore", uri), Some(json!({"reason": "deleted by mistake"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["data"], json!({"updated_by": ["required"]}));
+
+    let (status, body) = call(&app, Method::POST, &format!("{}/restore", uri), Some(json!({"updated_by": "editor", "reason": "deleted by mistake"}))).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["data"]["deleted_on"], Value::Null);
+
+    let (status, _) = call(&app, Method::POST, &format!("{}/restore", uri), Some(json!({"updated_by": "editor", "reason": "deleted by mistake"}))).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, _) = call(&app, Method::DELETE, "/countries/99", Some(json!({"deleted_by": "editor", "reason": "not there"}))).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}